@@ -0,0 +1,170 @@
+use std::collections::HashMap;
+
+use crate::union_find::RollbackUnionFind;
+
+/// Offline dynamic connectivity: records a sequence of edge additions, edge removals and
+/// connectivity queries, and then answers all queries at once.
+///
+/// Every edge is alive during an interval of queries. The intervals are inserted into a segment
+/// tree over the queries, which is then traversed depth first while joining the edges of each node
+/// in a [`RollbackUnionFind`] and rolling them back on the way up.
+///
+/// # Time complexity
+/// *O*(*q* log *q* log *n*) for *q* operations on *n* nodes.
+///
+/// # Examples
+/// ```rust
+/// # use algorithms::DynamicConnectivity;
+/// let mut dc = DynamicConnectivity::new(3);
+/// dc.add_edge(0, 1);
+/// dc.add_edge(1, 2);
+/// dc.connected(0, 2);
+/// dc.remove_edge(0, 1);
+/// dc.connected(0, 2);
+/// assert_eq!(vec![true, false], dc.solve());
+/// ```
+pub struct DynamicConnectivity {
+    size: usize,
+    queries: Vec<(usize, usize)>,
+    // edge -> the query indices at which copies of it were added
+    open: HashMap<(usize, usize), Vec<usize>>,
+    // (edge, first query, end query)
+    intervals: Vec<((usize, usize), usize, usize)>,
+}
+
+impl DynamicConnectivity {
+    pub fn new(size: usize) -> Self {
+        Self {
+            size,
+            queries: vec![],
+            open: HashMap::new(),
+            intervals: vec![],
+        }
+    }
+
+    fn key(a: usize, b: usize) -> (usize, usize) {
+        (a.min(b), a.max(b))
+    }
+
+    pub fn add_edge(&mut self, a: usize, b: usize) {
+        self.open
+            .entry(Self::key(a, b))
+            .or_default()
+            .push(self.queries.len());
+    }
+
+    /// Removes one copy of the edge between `a` and `b`, which must have been added before.
+    pub fn remove_edge(&mut self, a: usize, b: usize) {
+        let key = Self::key(a, b);
+        let start = self
+            .open
+            .get_mut(&key)
+            .and_then(Vec::pop)
+            .expect("removed an edge that doesn't exist");
+        self.intervals.push((key, start, self.queries.len()));
+    }
+
+    /// Asks whether `a` and `b` are connected at this point, the answer ends up in the
+    /// corresponding position of the result of `solve`.
+    pub fn connected(&mut self, a: usize, b: usize) {
+        self.queries.push((a, b));
+    }
+
+    pub fn solve(mut self) -> Vec<bool> {
+        let q = self.queries.len();
+        for (&key, starts) in self.open.iter() {
+            for &start in starts {
+                self.intervals.push((key, start, q));
+            }
+        }
+        if q == 0 {
+            return vec![];
+        }
+
+        let halflen = q.next_power_of_two();
+        let mut edges = vec![vec![]; halflen * 2];
+        for &(edge, start, end) in &self.intervals {
+            let (mut l, mut r) = (start + halflen, end + halflen);
+            while l < r {
+                if l & 1 == 1 {
+                    edges[l].push(edge);
+                    l += 1;
+                }
+                if r & 1 == 1 {
+                    r -= 1;
+                    edges[r].push(edge);
+                }
+                l /= 2;
+                r /= 2;
+            }
+        }
+
+        let mut uf = RollbackUnionFind::new(self.size);
+        let mut answers = vec![false; q];
+        self.visit(1, halflen, &edges, &mut uf, &mut answers);
+        answers
+    }
+
+    fn visit(
+        &self,
+        i: usize,
+        halflen: usize,
+        edges: &[Vec<(usize, usize)>],
+        uf: &mut RollbackUnionFind,
+        answers: &mut [bool],
+    ) {
+        let snapshot = uf.snapshot();
+        for &(a, b) in &edges[i] {
+            uf.join(a, b);
+        }
+        if i >= halflen {
+            if let Some(&(a, b)) = self.queries.get(i - halflen) {
+                answers[i - halflen] = uf.same(a, b);
+            }
+        } else {
+            self.visit(i * 2, halflen, edges, uf, answers);
+            self.visit(i * 2 + 1, halflen, edges, uf, answers);
+        }
+        uf.rollback(snapshot);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::UnionFind;
+
+    #[test]
+    fn generated() {
+        use rand::Rng;
+
+        let mut r = rand_pcg::Pcg32::new(0xcafef00dd15ea5e5, 0xa02bdbf7bb3c0a7);
+        let n = 12;
+        let mut dc = DynamicConnectivity::new(n);
+        let mut edges: Vec<(usize, usize)> = vec![];
+        let mut expected = vec![];
+        for _ in 0..500 {
+            match r.gen_range(0..3) {
+                0 => {
+                    let (a, b) = (r.gen_range(0..n), r.gen_range(0..n));
+                    dc.add_edge(a, b);
+                    edges.push((a, b));
+                }
+                1 if !edges.is_empty() => {
+                    let (a, b) = edges.swap_remove(r.gen_range(0..edges.len()));
+                    dc.remove_edge(b, a);
+                }
+                _ => {
+                    let (a, b) = (r.gen_range(0..n), r.gen_range(0..n));
+                    dc.connected(a, b);
+                    let mut uf = UnionFind::<Vec<usize>>::new(n);
+                    for &(u, v) in &edges {
+                        uf.join(u, v);
+                    }
+                    expected.push(uf.same(a, b));
+                }
+            }
+        }
+        assert_eq!(expected, dc.solve());
+    }
+}
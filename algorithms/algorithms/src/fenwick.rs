@@ -8,61 +8,390 @@
 // inc        ← {⍵+(⍳∊upidxs∘⍺)≢⍵} ⍝ O(n) time complexity (which destroys the whole purpose)
 // sum        ← {+/⍵[dnidxs ⍺-1]}  ⍝ O(log n) time complexity
 
-use std::ops::{AddAssign, Index, IndexMut};
+use std::iter::successors;
+use std::ops::{Index, IndexMut, Range};
+
+use crate::monoid::{Monoid, Sum};
+use crate::num::{AddGroup, Cancellative, One, Ring, Zero};
 
 fn lsb(x: usize) -> usize {
     (x as isize & -(x as isize)) as usize
 }
 
-pub struct FenwickTree<A = Vec<usize>> {
-    pub a: A,
+/// The nodes that together hold the prefix `[0, i)`.
+fn dnidxs(i: usize) -> impl Iterator<Item = usize> {
+    let last = i.checked_sub(1);
+    last.map(|_| 0)
+        .into_iter()
+        .chain(successors(last, |&j| Some(j - lsb(j))).take_while(|&j| j != 0))
+}
+
+/// The nodes that hold element `i` in a tree of length `len`.
+fn upidxs(i: usize, len: usize) -> impl Iterator<Item = usize> {
+    successors(Some(i), move |&j| (j != 0).then(|| j + lsb(j))).take_while(move |&j| j < len)
 }
 
-impl<T: Copy + Default> FenwickTree<Vec<T>> {
+/// A fenwick tree over the monoid `M`, which must also be commutative since the nodes are combined
+/// in reverse order.
+pub struct FenwickTree<A = Vec<usize>, M = Sum> {
+    a: A,
+    m: M,
+}
+
+impl<T: Copy, M: Monoid<T> + Default> FenwickTree<Vec<T>, M> {
     pub fn new(size: usize) -> Self {
+        Self::with_monoid(size, M::default())
+    }
+    /// Builds a tree containing `xs` in *O*(*n*).
+    pub fn from_slice(xs: &[T]) -> Self {
+        Self::from_slice_with_monoid(xs, M::default())
+    }
+}
+
+impl<T: Copy, M: Monoid<T>> FenwickTree<Vec<T>, M> {
+    pub fn with_monoid(size: usize, m: M) -> Self {
         Self {
-            a: vec![T::default(); size],
+            a: vec![m.identity(); size],
+            m,
+        }
+    }
+    pub fn from_slice_with_monoid(xs: &[T], m: M) -> Self {
+        let mut tree = Self { a: xs.to_vec(), m };
+        // every node is complete once all nodes below it have been pushed up
+        for i in 1..xs.len() {
+            let parent = i + lsb(i);
+            if parent < xs.len() {
+                tree.a[parent] = tree.m.op(tree.a[parent], tree.a[i]);
+            }
         }
+        tree
     }
 }
 
-impl<T: Copy + Default, const N: usize> FenwickTree<[T; N]> {
+impl<T: Copy, M: Monoid<T> + Default, const N: usize> FenwickTree<[T; N], M> {
     pub fn new() -> Self {
+        Self::with_monoid(M::default())
+    }
+}
+
+impl<T: Copy, M: Monoid<T> + Default, const N: usize> Default for FenwickTree<[T; N], M> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Copy, M: Monoid<T>, const N: usize> FenwickTree<[T; N], M> {
+    pub fn with_monoid(m: M) -> Self {
         Self {
-            a: [T::default(); N],
+            a: [m.identity(); N],
+            m,
         }
     }
 }
 
-impl<A, T> FenwickTree<A>
+impl<A, T, M> FenwickTree<A, M>
 where
     A: Index<usize, Output = T>,
     A: IndexMut<usize>,
     A: AsRef<[T]>, // for .len()
-    T: Copy + AddAssign + Default,
+    T: Copy,
+    M: Monoid<T>,
 {
-    pub fn prefix_sum(&self, mut i: usize) -> T {
-        i = match i.checked_sub(1) {
-            Some(i) => i,
-            None => return T::default(), // there is no zero trait in std
-        };
-        let mut sum = self.a[0];
-        while i != 0 {
-            sum += self.a[i];
-            i -= lsb(i);
+    pub fn prefix_sum(&self, i: usize) -> T {
+        dnidxs(i).fold(self.m.identity(), |sum, i| self.m.op(sum, self.a[i]))
+    }
+
+    pub fn add(&mut self, i: usize, d: T) {
+        for i in upidxs(i, self.a.as_ref().len()) {
+            self.a[i] = self.m.op(self.a[i], d);
         }
-        sum
     }
+}
 
-    pub fn add(&mut self, mut i: usize, d: T) {
-        if i == 0 {
-            self.a[0] += d;
-            return;
+impl<A, T, M> FenwickTree<A, M>
+where
+    A: Index<usize, Output = T>,
+    A: IndexMut<usize>,
+    A: AsRef<[T]>,
+    T: Copy + PartialOrd,
+    M: Monoid<T>,
+{
+    /// Given that `prefix_sum` is non-decreasing, e.g. because all elements are non-negative,
+    /// returns the smallest `i` such that `prefix_sum(i) >= target`, or `None` if there is no such
+    /// `i`.
+    ///
+    /// # Time complexity
+    /// *O*(log *n*)
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use algorithms::FenwickTree;
+    /// let mut tree = FenwickTree::<Vec<u32>>::new(8);
+    /// tree.add(2, 3);
+    /// tree.add(5, 1);
+    /// assert_eq!(Some(3), tree.lower_bound(2));
+    /// assert_eq!(Some(6), tree.lower_bound(4));
+    /// assert_eq!(None, tree.lower_bound(5));
+    /// ```
+    pub fn lower_bound(&self, target: T) -> Option<usize> {
+        let len = self.a.as_ref().len();
+        let mut sum = self.m.identity();
+        if sum >= target {
+            return Some(0);
+        }
+        if len == 0 {
+            return None;
+        }
+        sum = self.m.op(sum, self.a[0]);
+        if sum >= target {
+            return Some(1);
         }
-        while i < self.a.as_ref().len() {
-            self.a[i] += d;
-            i += lsb(i);
+        let mut i = 0;
+        let mut step = (len - 1).checked_next_power_of_two().unwrap_or(0);
+        // i is the largest node for which prefix_sum(i + 1) < target
+        while step != 0 {
+            if i + step < len {
+                let next = self.m.op(sum, self.a[i + step]);
+                if next < target {
+                    i += step;
+                    sum = next;
+                }
+            }
+            step /= 2;
         }
+        (i + 1 < len).then(|| i + 2)
+    }
+}
+
+impl<A, T> FenwickTree<A, Sum>
+where
+    A: Index<usize, Output = T>,
+    A: IndexMut<usize>,
+    A: AsRef<[T]>,
+    T: Zero + One + PartialOrd,
+{
+    /// Treating the tree as a multiset where element `i` is the number of occurrences of `i`,
+    /// returns the `k`:th smallest member (counting from zero).
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use algorithms::FenwickTree;
+    /// let mut tree = FenwickTree::<Vec<usize>>::new(10);
+    /// for x in [7, 2, 7, 4] {
+    ///     tree.add(x, 1);
+    /// }
+    /// assert_eq!(Some(2), tree.kth(0));
+    /// assert_eq!(Some(7), tree.kth(3));
+    /// assert_eq!(None, tree.kth(4));
+    /// ```
+    pub fn kth(&self, k: T) -> Option<usize> {
+        self.lower_bound(k + T::ONE).map(|i| i - 1)
+    }
+}
+
+impl<A, T> FenwickTree<A, Sum>
+where
+    A: Index<usize, Output = T>,
+    A: IndexMut<usize>,
+    A: AsRef<[T]>,
+    T: Cancellative,
+{
+    /// The sum of all elements in `r`.
+    pub fn range_sum(&self, r: Range<usize>) -> T {
+        self.prefix_sum(r.end) - self.prefix_sum(r.start)
+    }
+
+    pub fn get(&self, i: usize) -> T {
+        self.range_sum(i..i + 1)
+    }
+
+    pub fn set(&mut self, i: usize, value: T)
+    where
+        T: AddGroup,
+    {
+        let d = value - self.get(i);
+        self.add(i, d);
+    }
+
+    /// Like [`FenwickTree::set`], but for ordered types without negation, such as the unsigned
+    /// integers. Only non-negative differences are formed: a decrease is subtracted from the nodes
+    /// holding `i`, which all contain the old value.
+    pub fn set_ordered(&mut self, i: usize, value: T)
+    where
+        T: PartialOrd,
+    {
+        let old = self.get(i);
+        if value >= old {
+            self.add(i, value - old);
+        } else {
+            let d = old - value;
+            for i in upidxs(i, self.a.as_ref().len()) {
+                self.a[i] = self.a[i] - d;
+            }
+        }
+    }
+}
+
+/// A fenwick tree supporting adding to and summing over whole ranges in *O*(log *n*), using two
+/// ordinary fenwick trees: with `d` added to `[l, r)`, the sum of `[0, i)` is
+/// `linear.prefix_sum(i) * i - constant.prefix_sum(i)`.
+///
+/// The elements must be a [`Ring`], so unsigned types, for which adding to a range would have to
+/// subtract at its end, are rejected:
+/// ```compile_fail
+/// # use algorithms::RangeFenwickTree;
+/// RangeFenwickTree::<Vec<u64>>::new(5).range_add(1..3, 2);
+/// ```
+pub struct RangeFenwickTree<A = Vec<i64>> {
+    linear: FenwickTree<A>,
+    constant: FenwickTree<A>,
+}
+
+impl<T: Zero> RangeFenwickTree<Vec<T>> {
+    pub fn new(size: usize) -> Self {
+        Self {
+            linear: FenwickTree::<Vec<T>>::new(size),
+            constant: FenwickTree::<Vec<T>>::new(size),
+        }
+    }
+}
+
+impl<T: Zero, const N: usize> RangeFenwickTree<[T; N]> {
+    pub fn new() -> Self {
+        Self {
+            linear: FenwickTree::<[T; N]>::new(),
+            constant: FenwickTree::<[T; N]>::new(),
+        }
+    }
+}
+
+impl<T: Zero, const N: usize> Default for RangeFenwickTree<[T; N]> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<A, T> RangeFenwickTree<A>
+where
+    A: Index<usize, Output = T>,
+    A: IndexMut<usize>,
+    A: AsRef<[T]>,
+    T: Ring + TryFrom<usize>,
+{
+    fn index(i: usize) -> T {
+        T::try_from(i).unwrap_or_else(|_| panic!("index {} doesn't fit in the element type", i))
+    }
+
+    pub fn range_add(&mut self, r: Range<usize>, d: T) {
+        self.linear.add(r.start, d);
+        self.linear.add(r.end, -d);
+        self.constant.add(r.start, d * Self::index(r.start));
+        self.constant.add(r.end, -(d * Self::index(r.end)));
+    }
+
+    pub fn prefix_sum(&self, i: usize) -> T {
+        self.linear.prefix_sum(i) * Self::index(i) - self.constant.prefix_sum(i)
+    }
+
+    pub fn range_sum(&self, r: Range<usize>) -> T {
+        self.prefix_sum(r.end) - self.prefix_sum(r.start)
+    }
+}
+
+/// A two dimensional fenwick tree, stored as rows of one dimensional fenwick trees, e.g.
+/// `FenwickTree2D<Vec<Vec<i64>>>` or `FenwickTree2D<[[i64; W]; H]>`.
+pub struct FenwickTree2D<A = Vec<Vec<usize>>, M = Sum> {
+    a: A,
+    m: M,
+}
+
+impl<T: Copy, M: Monoid<T> + Default> FenwickTree2D<Vec<Vec<T>>, M> {
+    pub fn new(height: usize, width: usize) -> Self {
+        Self::with_monoid(height, width, M::default())
+    }
+}
+
+impl<T: Copy, M: Monoid<T>> FenwickTree2D<Vec<Vec<T>>, M> {
+    pub fn with_monoid(height: usize, width: usize, m: M) -> Self {
+        Self {
+            a: vec![vec![m.identity(); width]; height],
+            m,
+        }
+    }
+}
+
+impl<T: Copy, M: Monoid<T> + Default, const W: usize, const H: usize>
+    FenwickTree2D<[[T; W]; H], M>
+{
+    pub fn new() -> Self {
+        Self::with_monoid(M::default())
+    }
+}
+
+impl<T: Copy, M: Monoid<T> + Default, const W: usize, const H: usize> Default
+    for FenwickTree2D<[[T; W]; H], M>
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Copy, M: Monoid<T>, const W: usize, const H: usize> FenwickTree2D<[[T; W]; H], M> {
+    pub fn with_monoid(m: M) -> Self {
+        Self {
+            a: [[m.identity(); W]; H],
+            m,
+        }
+    }
+}
+
+impl<A, R, T, M> FenwickTree2D<A, M>
+where
+    A: Index<usize, Output = R>,
+    A: IndexMut<usize>,
+    A: AsRef<[R]>, // for .len()
+    R: Index<usize, Output = T>,
+    R: IndexMut<usize>,
+    R: AsRef<[T]>,
+    T: Copy,
+    M: Monoid<T>,
+{
+    fn width(&self) -> usize {
+        self.a.as_ref().first().map_or(0, |r| r.as_ref().len())
+    }
+
+    /// The sum of all elements in the rectangle `[0, i) x [0, j)`.
+    pub fn prefix_sum(&self, i: usize, j: usize) -> T {
+        dnidxs(i).fold(self.m.identity(), |sum, i| {
+            dnidxs(j).fold(sum, |sum, j| self.m.op(sum, self.a[i][j]))
+        })
+    }
+
+    pub fn add(&mut self, i: usize, j: usize, d: T) {
+        let width = self.width();
+        for i in upidxs(i, self.a.as_ref().len()) {
+            for j in upidxs(j, width) {
+                self.a[i][j] = self.m.op(self.a[i][j], d);
+            }
+        }
+    }
+}
+
+impl<A, R, T> FenwickTree2D<A, Sum>
+where
+    A: Index<usize, Output = R>,
+    A: IndexMut<usize>,
+    A: AsRef<[R]>,
+    R: Index<usize, Output = T>,
+    R: IndexMut<usize>,
+    R: AsRef<[T]>,
+    T: Cancellative,
+{
+    /// The sum of all elements in the rectangle `rows x cols`, taken as the difference of two
+    /// strips of `rows` so that it works for unsigned types too.
+    pub fn rect_sum(&self, rows: Range<usize>, cols: Range<usize>) -> T {
+        let strip = |j| self.prefix_sum(rows.end, j) - self.prefix_sum(rows.start, j);
+        strip(cols.end) - strip(cols.start)
     }
 }
 
@@ -102,4 +431,175 @@ mod tests {
             .collect();
         assert_eq!(&[1, 2, 4, 8, 16, 32], idxs.as_slice());
     }
+
+    #[test]
+    fn indices() {
+        assert_eq!(Vec::<usize>::new(), dnidxs(0).collect::<Vec<_>>());
+        assert_eq!(vec![0], dnidxs(1).collect::<Vec<_>>());
+        assert_eq!(vec![0, 6, 4], dnidxs(7).collect::<Vec<_>>());
+        assert_eq!(vec![0], upidxs(0, 10).collect::<Vec<_>>());
+        assert_eq!(vec![3, 4, 8], upidxs(3, 10).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn other_monoids() {
+        use crate::monoid::{Max, Xor};
+
+        let mut tree = FenwickTree::<Vec<i32>, Max>::new(6);
+        tree.add(3, 7);
+        tree.add(1, 2);
+        assert_eq!(i32::MIN, tree.prefix_sum(1));
+        assert_eq!(2, tree.prefix_sum(3));
+        assert_eq!(7, tree.prefix_sum(6));
+
+        let mut tree = FenwickTree::<[u8; 4], Xor>::new();
+        tree.add(0, 0b101);
+        tree.add(2, 0b110);
+        assert_eq!(0b011, tree.prefix_sum(3));
+    }
+
+    #[test]
+    fn range_sum() {
+        let mut tree = FenwickTree::<Vec<i32>>::new(6);
+        tree.add(1, 4);
+        tree.add(3, -2);
+        tree.add(5, 7);
+        assert_eq!(2, tree.range_sum(1..4));
+        assert_eq!(-2, tree.range_sum(2..4));
+        assert_eq!(5, tree.range_sum(2..6));
+        assert_eq!(0, tree.range_sum(4..4));
+    }
+
+    #[test]
+    fn from_slice() {
+        for len in 0..40 {
+            let xs: Vec<i64> = (0..len).map(|i| (i * 37 % 11) as i64 - 5).collect();
+            let tree = FenwickTree::<Vec<i64>>::from_slice(&xs);
+            let mut added = FenwickTree::<Vec<i64>>::new(len);
+            for (i, &x) in xs.iter().enumerate() {
+                added.add(i, x);
+            }
+            assert_eq!(added.a, tree.a);
+            for (i, &x) in xs.iter().enumerate() {
+                assert_eq!(x, tree.get(i));
+            }
+        }
+    }
+
+    #[test]
+    fn set() {
+        let mut tree = FenwickTree::<Vec<i32>>::from_slice(&[4, 1, 7, 3]);
+        tree.set(2, -2);
+        tree.set(0, 0);
+        assert_eq!(-2, tree.get(2));
+        assert_eq!(2, tree.prefix_sum(4));
+        assert_eq!(-1, tree.range_sum(1..3));
+    }
+
+    #[test]
+    fn set_ordered() {
+        let mut tree = FenwickTree::<Vec<usize>>::from_slice(&[4, 1, 7, 3]);
+        tree.set_ordered(2, 5);
+        tree.set_ordered(0, 0);
+        tree.set_ordered(3, 6);
+        assert_eq!(5, tree.get(2));
+        assert_eq!(12, tree.prefix_sum(4));
+        assert_eq!(6, tree.range_sum(1..3));
+    }
+
+    #[test]
+    fn lower_bound() {
+        for len in 0..20 {
+            let xs: Vec<u32> = (0..len).map(|i| ((i + len) * 7 % 3) as u32).collect();
+            let mut tree = FenwickTree::<Vec<u32>>::new(len);
+            for (i, &x) in xs.iter().enumerate() {
+                tree.add(i, x);
+            }
+            let total: u32 = xs.iter().sum();
+            for target in 0..total + 3 {
+                let expected = (0..=len).find(|&i| xs[..i].iter().sum::<u32>() >= target);
+                assert_eq!(expected, tree.lower_bound(target), "{} {}", len, target);
+            }
+        }
+    }
+
+    #[test]
+    fn order_statistics() {
+        let mut tree = FenwickTree::<[usize; 16]>::new();
+        let mut xs = vec![];
+        for x in [3, 9, 0, 15, 9, 4, 4, 4, 12] {
+            tree.add(x, 1);
+            xs.push(x);
+            xs.sort();
+            for (k, &x) in xs.iter().enumerate() {
+                assert_eq!(Some(x), tree.kth(k));
+            }
+            assert_eq!(None, tree.kth(xs.len()));
+        }
+    }
+
+    #[test]
+    fn range_add_range_sum() {
+        use rand::Rng;
+
+        let mut r = rand_pcg::Pcg32::new(0xcafef00dd15ea5e5, 0xa02bdbf7bb3c0a7);
+        let mut xs = [0i64; 23];
+        let mut tree = RangeFenwickTree::<Vec<i64>>::new(xs.len());
+        let mut array_tree = RangeFenwickTree::<[i64; 23]>::new();
+        for _ in 0..1000 {
+            let i = r.gen_range(0..xs.len());
+            let j = r.gen_range(i..=xs.len());
+            if r.gen() {
+                let d = r.gen_range(-50..50);
+                xs[i..j].iter_mut().for_each(|x| *x += d);
+                tree.range_add(i..j, d);
+                array_tree.range_add(i..j, d);
+            } else {
+                let expected: i64 = xs[i..j].iter().sum();
+                assert_eq!(expected, tree.range_sum(i..j), "{}..{}", i, j);
+                assert_eq!(expected, array_tree.range_sum(i..j), "{}..{}", i, j);
+            }
+        }
+    }
+
+    #[test]
+    fn two_dimensional() {
+        let mut grid = [[0i32; 7]; 5];
+        let mut tree = FenwickTree2D::<Vec<Vec<i32>>>::new(5, 7);
+        let mut array_tree = FenwickTree2D::<[[i32; 7]; 5]>::new();
+        for (k, (i, j)) in [(0, 0), (4, 6), (2, 3), (1, 0), (2, 3), (3, 5), (0, 6)]
+            .into_iter()
+            .enumerate()
+        {
+            let d = k as i32 * 3 - 4;
+            grid[i][j] += d;
+            tree.add(i, j, d);
+            array_tree.add(i, j, d);
+        }
+        for i0 in 0..=5 {
+            for i1 in i0..=5 {
+                for j0 in 0..=7 {
+                    for j1 in j0..=7 {
+                        let expected: i32 = grid[i0..i1]
+                            .iter()
+                            .map(|r| r[j0..j1].iter().sum::<i32>())
+                            .sum();
+                        assert_eq!(expected, tree.rect_sum(i0..i1, j0..j1));
+                        assert_eq!(expected, array_tree.rect_sum(i0..i1, j0..j1));
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn two_dimensional_unsigned() {
+        let mut tree = FenwickTree2D::<Vec<Vec<usize>>>::new(3, 3);
+        tree.add(0, 0, 5);
+        tree.add(1, 1, 2);
+        tree.add(2, 2, 1);
+        assert_eq!(2, tree.rect_sum(1..2, 0..3));
+        assert_eq!(3, tree.rect_sum(1..3, 1..3));
+        assert_eq!(0, tree.rect_sum(0..1, 1..3));
+    }
 }
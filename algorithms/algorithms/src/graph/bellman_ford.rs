@@ -0,0 +1,272 @@
+use std::collections::VecDeque;
+
+use super::Graph;
+use crate::num::{CheckedAdd, Zero};
+
+/// The length of a shortest path, which may be unbounded below if the path can pass through a
+/// negative cycle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Distance<W> {
+    Finite(W),
+    NegInfinity,
+    Unreachable,
+}
+
+impl<W> Distance<W> {
+    pub fn finite(self) -> Option<W> {
+        match self {
+            Distance::Finite(d) => Some(d),
+            _ => None,
+        }
+    }
+}
+
+/// Shortest paths from a source in a graph which may have negative weights, see [`bellman_ford`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BellmanFord<W> {
+    dist: Vec<Distance<W>>,
+    pred: Vec<Option<usize>>,
+    cycle: Option<Vec<usize>>,
+}
+
+impl<W: Copy> BellmanFord<W> {
+    pub fn dist(&self, v: usize) -> Distance<W> {
+        self.dist[v]
+    }
+
+    /// The nodes of a shortest path from the source to `v`, both included, if `v` has a finite
+    /// distance.
+    pub fn path_to(&self, v: usize) -> Option<Vec<usize>> {
+        self.dist[v].finite()?;
+        let mut path = vec![v];
+        let mut u = v;
+        while let Some(p) = self.pred[u] {
+            path.push(p);
+            u = p;
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    /// A negative cycle reachable from the source, see [`negative_cycle`].
+    pub fn negative_cycle(&self) -> Option<&[usize]> {
+        self.cycle.as_deref()
+    }
+}
+
+/// Shortest paths from `source` in a graph with possibly negative weights. Nodes which can be
+/// reached through a negative cycle get the distance [`Distance::NegInfinity`]. Paths whose length
+/// overflows `W` are treated as if they didn't exist.
+///
+/// # Time complexity
+/// *O*(*n* *m*), but it stops early once no distance changes.
+///
+/// # Examples
+/// ```rust
+/// # use algorithms::graph::{bellman_ford, Distance, GraphBuilder};
+/// let mut g = GraphBuilder::new(5);
+/// g.add_edge(0, 1, 4i64);
+/// g.add_edge(0, 2, 5);
+/// g.add_edge(2, 1, -3);
+/// g.add_edge(1, 3, 1);
+/// g.add_edge(3, 1, -2);
+/// let paths = bellman_ford(&g.build(), 0);
+/// assert_eq!(Distance::Finite(5), paths.dist(2));
+/// assert_eq!(Distance::NegInfinity, paths.dist(1));
+/// assert_eq!(Distance::Unreachable, paths.dist(4));
+/// assert_eq!(Some(vec![0, 2]), paths.path_to(2));
+/// let cycle = paths.negative_cycle().unwrap();
+/// assert!(cycle == [1, 3] || cycle == [3, 1]);
+/// ```
+pub fn bellman_ford<W>(g: &Graph<W>, source: usize) -> BellmanFord<W>
+where
+    W: Copy + Ord + Zero + CheckedAdd,
+{
+    let mut dist = vec![None; g.len()];
+    dist[source] = Some(W::ZERO);
+    let (dist, pred, relaxed) = relax(g, dist);
+
+    let cycle = relaxed.first().map(|&v| extract_cycle(&pred, v));
+    let mut neg = vec![false; g.len()];
+    let mut queue = VecDeque::new();
+    for v in relaxed {
+        if !neg[v] {
+            neg[v] = true;
+            queue.push_back(v);
+        }
+    }
+    while let Some(u) = queue.pop_front() {
+        for &v in g.neighbors(u) {
+            if !neg[v] {
+                neg[v] = true;
+                queue.push_back(v);
+            }
+        }
+    }
+
+    let dist = dist
+        .into_iter()
+        .zip(neg)
+        .map(|(d, neg)| match (d, neg) {
+            (_, true) => Distance::NegInfinity,
+            (Some(d), false) => Distance::Finite(d),
+            (None, false) => Distance::Unreachable,
+        })
+        .collect();
+    BellmanFord { dist, pred, cycle }
+}
+
+/// A negative cycle anywhere in the graph, as the nodes along it in order, or `None` if there is
+/// none.
+///
+/// # Time complexity
+/// *O*(*n* *m*)
+pub fn negative_cycle<W>(g: &Graph<W>) -> Option<Vec<usize>>
+where
+    W: Copy + Ord + Zero + CheckedAdd,
+{
+    let (_, pred, relaxed) = relax(g, vec![Some(W::ZERO); g.len()]);
+    relaxed.first().map(|&v| extract_cycle(&pred, v))
+}
+
+/// Distances from a virtual node with zero weight edges to every node, which are a feasible
+/// potential, i.e. `w + h[u] - h[v] >= 0` for every edge, or `None` if there is a negative cycle.
+pub(super) fn potentials<W>(g: &Graph<W>) -> Option<Vec<W>>
+where
+    W: Copy + Ord + Zero + CheckedAdd,
+{
+    let (dist, _, relaxed) = relax(g, vec![Some(W::ZERO); g.len()]);
+    relaxed
+        .is_empty()
+        .then(|| dist.into_iter().flatten().collect())
+}
+
+// Runs up to n rounds of relaxing every edge, returning the distances, the predecessors and the
+// nodes changed in the n:th round, which are all reachable from negative cycles.
+#[allow(clippy::type_complexity)]
+fn relax<W>(
+    g: &Graph<W>,
+    mut dist: Vec<Option<W>>,
+) -> (Vec<Option<W>>, Vec<Option<usize>>, Vec<usize>)
+where
+    W: Copy + Ord + CheckedAdd,
+{
+    let mut pred = vec![None; g.len()];
+    let mut changed = vec![];
+    for _ in 0..g.len() {
+        changed.clear();
+        for u in 0..g.len() {
+            let Some(d) = dist[u] else {
+                continue;
+            };
+            for (v, &w) in g.edges(u) {
+                let Some(nd) = d.checked_add(w) else {
+                    continue;
+                };
+                if dist[v].is_none_or(|old| nd < old) {
+                    dist[v] = Some(nd);
+                    pred[v] = Some(u);
+                    changed.push(v);
+                }
+            }
+        }
+        if changed.is_empty() {
+            break;
+        }
+    }
+    (dist, pred, changed)
+}
+
+// v was changed in the n:th round, so walking back n steps from it ends up on a negative cycle.
+fn extract_cycle(pred: &[Option<usize>], mut v: usize) -> Vec<usize> {
+    for _ in 0..pred.len() {
+        v = pred[v].expect("walked back to the source");
+    }
+    let mut cycle = vec![v];
+    let mut u = pred[v].unwrap();
+    while u != v {
+        cycle.push(u);
+        u = pred[u].unwrap();
+    }
+    cycle.reverse();
+    cycle
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::GraphBuilder;
+
+    #[test]
+    fn generated() {
+        use rand::Rng;
+
+        let mut r = rand_pcg::Pcg32::new(0xcafef00dd15ea5e5, 0xa02bdbf7bb3c0a7);
+        let mut cycles = 0;
+        for _ in 0..100 {
+            let n = r.gen_range(1..15);
+            let m = r.gen_range(0..3 * n);
+            let mut b = GraphBuilder::new(n);
+            let mut w = vec![vec![None; n]; n];
+            for _ in 0..m {
+                let (u, v, x) = (r.gen_range(0..n), r.gen_range(0..n), r.gen_range(-5..20i64));
+                b.add_edge(u, v, x);
+                w[u][v] = Some(w[u][v].map_or(x, |y: i64| y.min(x)));
+            }
+            let g = b.build();
+
+            // Floyd-Warshall
+            let mut d = w.clone();
+            for (u, row) in d.iter_mut().enumerate() {
+                row[u] = Some(row[u].map_or(0, |x| x.min(0)));
+            }
+            for k in 0..n {
+                for a in 0..n {
+                    for b in 0..n {
+                        if let (Some(x), Some(y)) = (d[a][k], d[k][b]) {
+                            if d[a][b].is_none_or(|z| x + y < z) {
+                                d[a][b] = Some(x + y);
+                            }
+                        }
+                    }
+                }
+            }
+            let neg = |a: usize, b: usize| {
+                (0..n).any(|k| d[a][k].is_some() && d[k][k] < Some(0) && d[k][b].is_some())
+            };
+
+            let paths = bellman_ford(&g, 0);
+            for (v, &d) in d[0].iter().enumerate() {
+                let expected = if neg(0, v) {
+                    Distance::NegInfinity
+                } else {
+                    d.map_or(Distance::Unreachable, Distance::Finite)
+                };
+                assert_eq!(expected, paths.dist(v));
+                if let Some(path) = paths.path_to(v) {
+                    let len = path.windows(2).map(|p| w[p[0]][p[1]].unwrap()).sum();
+                    assert_eq!(expected, Distance::Finite(len));
+                }
+            }
+
+            let check = |cycle: &[usize]| {
+                let len = (0..cycle.len())
+                    .map(|i| w[cycle[i]][cycle[(i + 1) % cycle.len()]].unwrap())
+                    .sum::<i64>();
+                assert!(len < 0);
+            };
+            assert_eq!((0..n).any(|v| neg(0, v)), paths.negative_cycle().is_some());
+            if let Some(cycle) = paths.negative_cycle() {
+                assert!(d[0][cycle[0]].is_some());
+                check(cycle);
+            }
+            let any = (0..n).any(|k| d[k][k] < Some(0));
+            assert_eq!(any, negative_cycle(&g).is_some());
+            if let Some(cycle) = negative_cycle(&g) {
+                cycles += 1;
+                check(&cycle);
+            }
+        }
+        assert!(cycles > 10);
+    }
+}
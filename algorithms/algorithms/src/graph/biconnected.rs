@@ -0,0 +1,340 @@
+use super::{Graph, GraphBuilder};
+
+/// Bridges, articulation points, 2-edge-connected components and biconnected components (blocks)
+/// of an undirected graph, see [`biconnectivity`].
+///
+/// Edges are referred to by the ids returned by
+/// [`GraphBuilder::add_undirected_edge`](super::GraphBuilder::add_undirected_edge).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Biconnectivity {
+    is_bridge: Vec<bool>,
+    is_articulation: Vec<bool>,
+    two_edge: Vec<usize>,
+    two_edge_count: usize,
+    // the block of every edge, usize::MAX for self loops
+    block: Vec<usize>,
+    blocks: Vec<Vec<usize>>,
+}
+
+impl Biconnectivity {
+    /// Whether removing the edge with id `e` disconnects its endpoints.
+    pub fn is_bridge(&self, e: usize) -> bool {
+        self.is_bridge[e]
+    }
+
+    /// The ids of all bridges in increasing order.
+    pub fn bridges(&self) -> Vec<usize> {
+        (0..self.is_bridge.len())
+            .filter(|&e| self.is_bridge[e])
+            .collect()
+    }
+
+    /// Whether removing `v` disconnects some of its neighbors from each other.
+    pub fn is_articulation_point(&self, v: usize) -> bool {
+        self.is_articulation[v]
+    }
+
+    /// All articulation points in increasing order.
+    pub fn articulation_points(&self) -> Vec<usize> {
+        (0..self.is_articulation.len())
+            .filter(|&v| self.is_articulation[v])
+            .collect()
+    }
+
+    pub fn num_two_edge_components(&self) -> usize {
+        self.two_edge_count
+    }
+
+    /// The index of the 2-edge-connected component of `v`, i.e. the component it's in once all
+    /// bridges are removed. Components are numbered in the order of their smallest node.
+    pub fn two_edge_component(&self, v: usize) -> usize {
+        self.two_edge[v]
+    }
+
+    pub fn num_blocks(&self) -> usize {
+        self.blocks.len()
+    }
+
+    /// The index of the biconnected component containing the edge with id `e`, i.e. the largest
+    /// set of edges containing it where every two edges lie on a common simple cycle. Self loops
+    /// aren't in any block.
+    pub fn block(&self, e: usize) -> Option<usize> {
+        Some(self.block[e]).filter(|&b| b != usize::MAX)
+    }
+
+    /// The nodes of every block in increasing order. Articulation points are the nodes which are
+    /// in more than one block.
+    pub fn blocks(&self) -> &[Vec<usize>] {
+        &self.blocks
+    }
+
+    /// The block-cut tree (a forest if the graph isn't connected): node `b` for every block `b`,
+    /// node `num_blocks() + k` for the `k`:th articulation point, and an edge between every
+    /// articulation point and the blocks it's in.
+    pub fn block_cut_tree(&self) -> Graph {
+        let points = self.articulation_points();
+        let mut tree = GraphBuilder::new(self.blocks.len() + points.len());
+        for (b, nodes) in self.blocks.iter().enumerate() {
+            for &v in nodes {
+                if let Ok(k) = points.binary_search(&v) {
+                    tree.add_undirected_edge(b, self.blocks.len() + k, ());
+                }
+            }
+        }
+        tree.build()
+    }
+}
+
+/// Finds the bridges, articulation points, 2-edge-connected components and blocks of an
+/// undirected graph by comparing the discovery time of every node with the earliest time reachable
+/// from its subtree of the depth first search (its lowlink). The search uses an explicit stack so
+/// long paths can't overflow the call stack.
+///
+/// Parallel edges form a cycle, so they are never bridges.
+///
+/// # Time complexity
+/// *O*(*n* + *m*)
+///
+/// # Examples
+/// ```rust
+/// # use algorithms::graph::{biconnectivity, GraphBuilder};
+/// // two triangles sharing node 2, with a tail 4 - 5
+/// let mut g = GraphBuilder::new(6);
+/// for (u, v) in [(0, 1), (1, 2), (2, 0), (2, 3), (3, 4), (4, 2), (4, 5)] {
+///     g.add_undirected_edge(u, v, ());
+/// }
+/// let bc = biconnectivity(&g.build());
+/// assert_eq!(vec![6], bc.bridges());
+/// assert_eq!(vec![2, 4], bc.articulation_points());
+/// assert_eq!(bc.two_edge_component(0), bc.two_edge_component(3));
+/// assert_ne!(bc.two_edge_component(4), bc.two_edge_component(5));
+/// assert_eq!(3, bc.num_blocks());
+/// assert_eq!(bc.block(0), bc.block(2));
+/// assert_ne!(bc.block(0), bc.block(3));
+/// assert_eq!(5, bc.block_cut_tree().len());
+/// ```
+pub fn biconnectivity<W>(g: &Graph<W>) -> Biconnectivity {
+    let n = g.len();
+    let m = g.num_edges();
+    let mut index = vec![usize::MAX; n];
+    let mut low = vec![0; n];
+    let mut parent_edge = vec![usize::MAX; n];
+    let mut is_bridge = vec![false; m];
+    let mut is_articulation = vec![false; n];
+    let mut block = vec![usize::MAX; m];
+    let mut blocks = vec![];
+    let mut time = 0;
+
+    // (node, how many of its edges have been looked at)
+    let mut calls = vec![];
+    // (edge, u, v) for the edges of the blocks which haven't been closed yet
+    let mut edges = vec![];
+    for s in 0..n {
+        if index[s] != usize::MAX {
+            continue;
+        }
+        index[s] = time;
+        low[s] = time;
+        time += 1;
+        calls.push((s, 0));
+        let mut root_children = 0;
+
+        while let Some(&(u, i)) = calls.last() {
+            if i < g.degree(u) {
+                calls.last_mut().unwrap().1 += 1;
+                let (v, e) = (g.neighbors(u)[i], g.edge_ids(u)[i]);
+                if e == parent_edge[u] {
+                    continue;
+                }
+                if index[v] == usize::MAX {
+                    index[v] = time;
+                    low[v] = time;
+                    time += 1;
+                    parent_edge[v] = e;
+                    edges.push((e, u, v));
+                    calls.push((v, 0));
+                } else if index[v] < index[u] {
+                    low[u] = low[u].min(index[v]);
+                    edges.push((e, u, v));
+                }
+                continue;
+            }
+
+            calls.pop();
+            let Some(&(p, _)) = calls.last() else {
+                continue;
+            };
+            low[p] = low[p].min(low[u]);
+            if low[u] > index[p] {
+                is_bridge[parent_edge[u]] = true;
+            }
+            if low[u] >= index[p] {
+                // p separates the subtree of u from the rest, which closes a block
+                if p == s {
+                    root_children += 1;
+                } else {
+                    is_articulation[p] = true;
+                }
+                let mut nodes = vec![];
+                loop {
+                    let (e, a, b) = edges.pop().unwrap();
+                    block[e] = blocks.len();
+                    nodes.extend([a, b]);
+                    if e == parent_edge[u] {
+                        break;
+                    }
+                }
+                nodes.sort_unstable();
+                nodes.dedup();
+                blocks.push(nodes);
+            }
+        }
+        is_articulation[s] = root_children >= 2;
+    }
+
+    let mut two_edge = vec![usize::MAX; n];
+    let mut two_edge_count = 0;
+    let mut stack = vec![];
+    for s in 0..n {
+        if two_edge[s] != usize::MAX {
+            continue;
+        }
+        two_edge[s] = two_edge_count;
+        stack.push(s);
+        while let Some(u) = stack.pop() {
+            for (&v, &e) in g.neighbors(u).iter().zip(g.edge_ids(u)) {
+                if !is_bridge[e] && two_edge[v] == usize::MAX {
+                    two_edge[v] = two_edge_count;
+                    stack.push(v);
+                }
+            }
+        }
+        two_edge_count += 1;
+    }
+
+    Biconnectivity {
+        is_bridge,
+        is_articulation,
+        two_edge,
+        two_edge_count,
+        block,
+        blocks,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::UnionFind;
+
+    fn connected(n: usize, edges: &[(usize, usize)], a: usize, b: usize) -> bool {
+        let mut uf = UnionFind::<Vec<usize>>::new(n);
+        for &(u, v) in edges {
+            uf.join(u, v);
+        }
+        uf.same(a, b)
+    }
+
+    // whether there is a simple path from `a` to `to` which doesn't use the edge `skip` but uses
+    // the edge `want`
+    fn path_through(
+        edges: &[(usize, usize)],
+        a: usize,
+        to: usize,
+        skip: usize,
+        want: usize,
+        visited: &mut Vec<usize>,
+        used: bool,
+    ) -> bool {
+        if a == to {
+            return used;
+        }
+        visited.push(a);
+        let found = edges.iter().enumerate().any(|(e, &(u, v))| {
+            let b = if u == a { v } else { u };
+            e != skip
+                && (u == a || v == a)
+                && !visited.contains(&b)
+                && path_through(edges, b, to, skip, want, visited, used || e == want)
+        });
+        visited.pop();
+        found
+    }
+
+    #[test]
+    fn generated() {
+        use rand::Rng;
+
+        let mut r = rand_pcg::Pcg32::new(0xcafef00dd15ea5e5, 0xa02bdbf7bb3c0a7);
+        for _ in 0..200 {
+            let n = r.gen_range(1..8);
+            let m = r.gen_range(0..2 * n);
+            let mut b = GraphBuilder::new(n);
+            let mut edges = vec![];
+            for _ in 0..m {
+                let (u, v) = (r.gen_range(0..n), r.gen_range(0..n));
+                b.add_undirected_edge(u, v, ());
+                edges.push((u, v));
+            }
+            let g = b.build();
+            let bc = biconnectivity(&g);
+
+            for (e, &(u, v)) in edges.iter().enumerate() {
+                let mut rest = edges.clone();
+                rest.remove(e);
+                assert_eq!(!connected(n, &rest, u, v), bc.is_bridge(e));
+            }
+
+            for x in 0..n {
+                let rest: Vec<_> = edges
+                    .iter()
+                    .copied()
+                    .filter(|&(u, v)| u != x && v != x)
+                    .collect();
+                let split = g.neighbors(x).iter().any(|&a| {
+                    g.neighbors(x)
+                        .iter()
+                        .any(|&b| a != x && b != x && !connected(n, &rest, a, b))
+                });
+                assert_eq!(split, bc.is_articulation_point(x));
+            }
+
+            let kept: Vec<_> = edges
+                .iter()
+                .enumerate()
+                .filter(|&(e, _)| !bc.is_bridge(e))
+                .map(|(_, &e)| e)
+                .collect();
+            for u in 0..n {
+                for v in 0..n {
+                    assert_eq!(
+                        connected(n, &kept, u, v),
+                        bc.two_edge_component(u) == bc.two_edge_component(v)
+                    );
+                }
+            }
+
+            for (e, &(a, b)) in edges.iter().enumerate() {
+                assert_eq!(a == b, bc.block(e).is_none());
+                for (f, &(c, d)) in edges.iter().enumerate() {
+                    if a == b || c == d || e == f {
+                        continue;
+                    }
+                    let cycle = path_through(&edges, b, a, e, f, &mut vec![], false);
+                    assert_eq!(cycle, bc.block(e) == bc.block(f), "{:?} {} {}", edges, e, f);
+                    assert!(bc.blocks()[bc.block(f).unwrap()].contains(&c));
+                }
+            }
+
+            let tree = bc.block_cut_tree();
+            assert_eq!(bc.num_blocks() + bc.articulation_points().len(), tree.len());
+            let mut uf = UnionFind::<Vec<usize>>::new(tree.len());
+            for (u, v, _) in tree.all_edges() {
+                assert!((u < bc.num_blocks()) != (v < bc.num_blocks()));
+                if u < v {
+                    assert!(uf.join(u, v));
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,314 @@
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+use super::Graph;
+use crate::num::{CheckedAdd, Zero};
+
+/// The distances from a source node along with a tree of shortest paths.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShortestPaths<W> {
+    source: usize,
+    dist: Vec<Option<W>>,
+    pred: Vec<Option<usize>>,
+}
+
+impl<W: Copy> ShortestPaths<W> {
+    pub fn source(&self) -> usize {
+        self.source
+    }
+
+    /// The length of a shortest path to `v`, or `None` if it's unreachable.
+    pub fn dist(&self, v: usize) -> Option<W> {
+        self.dist[v]
+    }
+
+    /// The node before `v` on a shortest path to it, or `None` for the source and unreachable
+    /// nodes.
+    pub fn pred(&self, v: usize) -> Option<usize> {
+        self.pred[v]
+    }
+
+    /// The nodes of a shortest path from the source to `v`, both included.
+    pub fn path_to(&self, v: usize) -> Option<Vec<usize>> {
+        self.dist[v]?;
+        let mut path = vec![v];
+        let mut u = v;
+        while let Some(p) = self.pred[u] {
+            path.push(p);
+            u = p;
+        }
+        path.reverse();
+        Some(path)
+    }
+}
+
+/// Shortest paths from `source` in a graph with non-negative weights. Paths whose length
+/// overflows `W` are treated as if they didn't exist.
+///
+/// # Time complexity
+/// *O*((*n* + *m*) log *n*)
+///
+/// # Examples
+/// ```rust
+/// # use algorithms::graph::{dijkstra, GraphBuilder};
+/// let mut g = GraphBuilder::new(4);
+/// g.add_edge(0, 1, 4u32);
+/// g.add_edge(0, 2, 1);
+/// g.add_edge(2, 1, 2);
+/// let paths = dijkstra(&g.build(), 0);
+/// assert_eq!(Some(3), paths.dist(1));
+/// assert_eq!(Some(vec![0, 2, 1]), paths.path_to(1));
+/// assert_eq!(None, paths.dist(3));
+/// ```
+pub fn dijkstra<W>(g: &Graph<W>, source: usize) -> ShortestPaths<W>
+where
+    W: Copy + Ord + Zero + CheckedAdd,
+{
+    search(g, source, W::ZERO, None, relax)
+}
+
+/// Like [`dijkstra`], but stops as soon as the distance to `target` is known. Nodes which are
+/// further away than `target` are reported as unreachable.
+pub fn dijkstra_to<W>(g: &Graph<W>, source: usize, target: usize) -> ShortestPaths<W>
+where
+    W: Copy + Ord + Zero + CheckedAdd,
+{
+    search(g, source, W::ZERO, Some(target), relax)
+}
+
+fn relax<W: Copy + Ord + Zero + CheckedAdd>(&w: &W, d: W) -> Option<W> {
+    debug_assert!(w >= W::ZERO, "dijkstra needs non-negative weights");
+    d.checked_add(w)
+}
+
+/// Earliest arrival times at every node when leaving `source` at time `start`, for edges which
+/// can only be taken at certain times.
+///
+/// `arrive(w, t)` is the earliest time the other end of an edge with weight `w` can be reached
+/// when standing at its start at time `t`, i.e. the earliest departure time after `t` plus the
+/// travel time, or `None` if the edge can't be taken any more. It must not decrease with `t`, i.e.
+/// waiting never lets you arrive earlier. The routes are given by [`ShortestPaths::path_to`].
+///
+/// # Time complexity
+/// *O*((*n* + *m*) log *n*) calls to `arrive`.
+///
+/// # Examples
+/// Edges `(t0, p, d)` depart at `t0`, `t0 + p`, `t0 + 2p`, ... and take `d` to traverse.
+/// ```rust
+/// # use algorithms::graph::{earliest_arrival, GraphBuilder};
+/// let mut g = GraphBuilder::new(3);
+/// g.add_edge(0, 1, (5u64, 10, 1));
+/// g.add_edge(1, 2, (0, 4, 2));
+/// g.add_edge(0, 2, (20, 0, 1));
+/// let times = earliest_arrival(&g.build(), 0, 7, |&(t0, p, d), t| {
+///     if t <= t0 {
+///         Some(t0 + d)
+///     } else if p == 0 {
+///         None
+///     } else {
+///         Some(t0 + (t - t0).div_ceil(p) * p + d)
+///     }
+/// });
+/// assert_eq!(Some(16), times.dist(1));
+/// assert_eq!(Some(18), times.dist(2));
+/// assert_eq!(Some(vec![0, 1, 2]), times.path_to(2));
+/// ```
+pub fn earliest_arrival<W, T, F>(
+    g: &Graph<W>,
+    source: usize,
+    start: T,
+    arrive: F,
+) -> ShortestPaths<T>
+where
+    T: Copy + Ord,
+    F: Fn(&W, T) -> Option<T>,
+{
+    search(g, source, start, None, arrive)
+}
+
+fn search<W, T, F>(
+    g: &Graph<W>,
+    source: usize,
+    start: T,
+    target: Option<usize>,
+    arrive: F,
+) -> ShortestPaths<T>
+where
+    T: Copy + Ord,
+    F: Fn(&W, T) -> Option<T>,
+{
+    let mut dist = vec![None; g.len()];
+    let mut pred = vec![None; g.len()];
+    let mut done = vec![false; g.len()];
+    let mut heap = BinaryHeap::new();
+    dist[source] = Some(start);
+    heap.push(Reverse((start, source)));
+
+    while let Some(Reverse((d, u))) = heap.pop() {
+        if done[u] {
+            continue;
+        }
+        done[u] = true;
+        if Some(u) == target {
+            break;
+        }
+        for (v, w) in g.edges(u) {
+            let Some(nd) = arrive(w, d) else {
+                continue;
+            };
+            if dist[v].is_none_or(|old| nd < old) {
+                dist[v] = Some(nd);
+                pred[v] = Some(u);
+                heap.push(Reverse((nd, v)));
+            }
+        }
+    }
+
+    if target.is_some() {
+        for v in 0..g.len() {
+            if !done[v] {
+                dist[v] = None;
+                pred[v] = None;
+            }
+        }
+    }
+
+    ShortestPaths { source, dist, pred }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::GraphBuilder;
+
+    #[test]
+    fn generated() {
+        use rand::Rng;
+
+        let mut r = rand_pcg::Pcg32::new(0xcafef00dd15ea5e5, 0xa02bdbf7bb3c0a7);
+        let n = 30;
+        let mut b = GraphBuilder::new(n);
+        let mut edges = vec![];
+        for _ in 0..80 {
+            let (u, v, w) = (r.gen_range(0..n), r.gen_range(0..n), r.gen_range(0..100u64));
+            b.add_edge(u, v, w);
+            edges.push((u, v, w));
+        }
+        let g = b.build();
+
+        // Bellman-Ford
+        let mut expected = vec![None; n];
+        expected[0] = Some(0);
+        for _ in 0..n {
+            for &(u, v, w) in &edges {
+                if let Some(d) = expected[u] {
+                    if expected[v].is_none_or(|old| d + w < old) {
+                        expected[v] = Some(d + w);
+                    }
+                }
+            }
+        }
+
+        let paths = dijkstra(&g, 0);
+        for (v, &expected) in expected.iter().enumerate() {
+            assert_eq!(expected, paths.dist(v));
+            if let Some(path) = paths.path_to(v) {
+                assert_eq!(Some(&0), path.first());
+                assert_eq!(Some(&v), path.last());
+                let len = path
+                    .windows(2)
+                    .map(|p| {
+                        g.edges(p[0])
+                            .filter(|&(v, _)| v == p[1])
+                            .map(|(_, &w)| w)
+                            .min()
+                            .unwrap()
+                    })
+                    .sum::<u64>();
+                assert_eq!(expected, Some(len));
+            }
+
+            let to = dijkstra_to(&g, 0, v);
+            assert_eq!(expected, to.dist(v));
+            assert_eq!(paths.path_to(v), to.path_to(v));
+        }
+    }
+
+    #[test]
+    fn overflow() {
+        let mut b = GraphBuilder::new(4);
+        b.add_edge(0, 1, 200u8);
+        b.add_edge(1, 2, 100);
+        b.add_edge(0, 3, 250);
+        b.add_edge(3, 2, 5);
+        let paths = dijkstra(&b.build(), 0);
+        assert_eq!(Some(255), paths.dist(2));
+        assert_eq!(Some(vec![0, 3, 2]), paths.path_to(2));
+
+        let mut b = GraphBuilder::new(3);
+        b.add_edge(0, 1, u32::MAX);
+        b.add_edge(1, 2, 1);
+        let paths = dijkstra(&b.build(), 0);
+        assert_eq!(Some(u32::MAX), paths.dist(1));
+        assert_eq!(None, paths.dist(2));
+        assert_eq!(None, paths.pred(2));
+        assert_eq!(None, paths.path_to(2));
+    }
+
+    #[test]
+    fn schedules() {
+        use rand::Rng;
+
+        let mut r = rand_pcg::Pcg32::new(0xcafef00dd15ea5e5, 0xa02bdbf7bb3c0a7);
+        let n = 25;
+        let mut b = GraphBuilder::new(n);
+        let mut edges = vec![];
+        for _ in 0..70 {
+            let (u, v) = (r.gen_range(0..n), r.gen_range(0..n));
+            let w = (
+                r.gen_range(0..50u32),
+                r.gen_range(0..10),
+                r.gen_range(0..20),
+            );
+            b.add_edge(u, v, w);
+            edges.push((u, v, w));
+        }
+        let arrive = |&(t0, p, d): &(u32, u32, u32), t: u32| {
+            if t <= t0 {
+                Some(t0 + d)
+            } else if p == 0 {
+                None
+            } else {
+                Some(t0 + (t - t0).div_ceil(p) * p + d)
+            }
+        };
+        let times = earliest_arrival(&b.build(), 0, 3, arrive);
+
+        let mut expected = vec![None; n];
+        expected[0] = Some(3);
+        for _ in 0..n {
+            for &(u, v, w) in &edges {
+                if let Some(t) = expected[u].and_then(|t| arrive(&w, t)) {
+                    if expected[v].is_none_or(|old| t < old) {
+                        expected[v] = Some(t);
+                    }
+                }
+            }
+        }
+        for (v, &expected) in expected.iter().enumerate() {
+            assert_eq!(expected, times.dist(v));
+            if let Some(path) = times.path_to(v) {
+                let mut t = 3;
+                for p in path.windows(2) {
+                    t = edges
+                        .iter()
+                        .filter(|e| (e.0, e.1) == (p[0], p[1]))
+                        .filter_map(|e| arrive(&e.2, t))
+                        .min()
+                        .unwrap();
+                }
+                assert_eq!(expected, Some(t));
+            }
+        }
+    }
+}
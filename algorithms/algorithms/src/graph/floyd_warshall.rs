@@ -0,0 +1,163 @@
+use super::{Distance, Graph};
+use crate::num::{CheckedAdd, Zero};
+
+/// Shortest paths between every pair of nodes, stored as dense `n` by `n` matrices of distances and
+/// of the next node to go to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AllPairs<W> {
+    len: usize,
+    dist: Vec<Distance<W>>,
+    next: Vec<Option<usize>>,
+}
+
+impl<W: Copy> AllPairs<W> {
+    pub(super) fn new(len: usize, dist: Vec<Distance<W>>, next: Vec<Option<usize>>) -> Self {
+        Self { len, dist, next }
+    }
+
+    /// The number of nodes.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn dist(&self, u: usize, v: usize) -> Distance<W> {
+        self.dist[u * self.len + v]
+    }
+
+    /// The node after `u` on a shortest path from `u` to `v`, or `None` if the distance isn't
+    /// finite. `u` itself if `u == v`.
+    pub fn next_hop(&self, u: usize, v: usize) -> Option<usize> {
+        self.next[u * self.len + v]
+    }
+
+    /// The nodes of a shortest path from `u` to `v`, both included, if the distance is finite.
+    pub fn path(&self, mut u: usize, v: usize) -> Option<Vec<usize>> {
+        self.dist(u, v).finite()?;
+        let mut path = vec![u];
+        while u != v {
+            u = self.next_hop(u, v)?;
+            path.push(u);
+        }
+        Some(path)
+    }
+}
+
+/// Shortest paths between all pairs of nodes in a graph with possibly negative weights. Pairs with
+/// a path through a negative cycle get the distance [`Distance::NegInfinity`]. Paths whose length
+/// overflows `W` are treated as if they didn't exist.
+///
+/// # Time complexity
+/// *O*(*n*³)
+///
+/// # Examples
+/// ```rust
+/// # use algorithms::graph::{floyd_warshall, Distance, GraphBuilder};
+/// let mut g = GraphBuilder::new(4);
+/// g.add_edge(0, 1, 2i64);
+/// g.add_edge(1, 2, -1);
+/// g.add_edge(0, 2, 4);
+/// g.add_edge(3, 3, -1);
+/// let paths = floyd_warshall(&g.build());
+/// assert_eq!(Distance::Finite(1), paths.dist(0, 2));
+/// assert_eq!(Some(vec![0, 1, 2]), paths.path(0, 2));
+/// assert_eq!(Distance::Unreachable, paths.dist(2, 0));
+/// assert_eq!(Distance::NegInfinity, paths.dist(3, 3));
+/// ```
+pub fn floyd_warshall<W>(g: &Graph<W>) -> AllPairs<W>
+where
+    W: Copy + Ord + Zero + CheckedAdd,
+{
+    let n = g.len();
+    let mut dist = vec![None; n * n];
+    let mut next = vec![None; n * n];
+    for u in 0..n {
+        dist[u * n + u] = Some(W::ZERO);
+        next[u * n + u] = Some(u);
+        for (v, &w) in g.edges(u) {
+            if dist[u * n + v].is_none_or(|d| w < d) {
+                dist[u * n + v] = Some(w);
+                next[u * n + v] = Some(v);
+            }
+        }
+    }
+
+    for k in 0..n {
+        for a in 0..n {
+            let Some(x) = dist[a * n + k] else {
+                continue;
+            };
+            for b in 0..n {
+                let Some(d) = dist[k * n + b].and_then(|y| x.checked_add(y)) else {
+                    continue;
+                };
+                if dist[a * n + b].is_none_or(|old| d < old) {
+                    dist[a * n + b] = Some(d);
+                    next[a * n + b] = next[a * n + k];
+                }
+            }
+        }
+    }
+
+    let mut result: Vec<_> = dist
+        .iter()
+        .map(|d| d.map_or(Distance::Unreachable, Distance::Finite))
+        .collect();
+    for k in (0..n).filter(|&k| dist[k * n + k].is_some_and(|d| d < W::ZERO)) {
+        for a in (0..n).filter(|&a| dist[a * n + k].is_some()) {
+            for b in (0..n).filter(|&b| dist[k * n + b].is_some()) {
+                result[a * n + b] = Distance::NegInfinity;
+                next[a * n + b] = None;
+            }
+        }
+    }
+
+    AllPairs::new(n, result, next)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{bellman_ford, GraphBuilder};
+
+    #[test]
+    fn generated() {
+        use rand::Rng;
+
+        let mut r = rand_pcg::Pcg32::new(0xcafef00dd15ea5e5, 0xa02bdbf7bb3c0a7);
+        for _ in 0..50 {
+            let n = r.gen_range(1..12);
+            let m = r.gen_range(0..3 * n);
+            let mut b = GraphBuilder::new(n);
+            for _ in 0..m {
+                let (u, v, w) = (r.gen_range(0..n), r.gen_range(0..n), r.gen_range(-3..20i64));
+                b.add_edge(u, v, w);
+            }
+            let g = b.build();
+
+            let paths = floyd_warshall(&g);
+            for u in 0..n {
+                let expected = bellman_ford(&g, u);
+                for v in 0..n {
+                    assert_eq!(expected.dist(v), paths.dist(u, v));
+                    if let Some(path) = paths.path(u, v) {
+                        let len = path
+                            .windows(2)
+                            .map(|p| {
+                                g.edges(p[0])
+                                    .filter(|&(v, _)| v == p[1])
+                                    .map(|(_, &w)| w)
+                                    .min()
+                                    .unwrap()
+                            })
+                            .sum::<i64>();
+                        assert_eq!(expected.dist(v), Distance::Finite(len));
+                    }
+                }
+            }
+        }
+    }
+}
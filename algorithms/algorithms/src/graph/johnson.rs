@@ -0,0 +1,120 @@
+use std::ops::Sub;
+
+use super::bellman_ford::potentials;
+use super::{dijkstra, AllPairs, Distance, Graph};
+use crate::num::{CheckedAdd, Zero};
+
+/// Shortest paths between all pairs of nodes in a graph with possibly negative weights, or `None`
+/// if it has a negative cycle.
+///
+/// The weights are made non-negative with potentials from Bellman-Ford, so that the shortest paths
+/// from each node can be found with Dijkstra. This is faster than [`floyd_warshall`] for sparse
+/// graphs, and returns the paths in the same format.
+///
+/// [`floyd_warshall`]: super::floyd_warshall
+///
+/// # Time complexity
+/// *O*(*n* *m* log *n*)
+///
+/// # Examples
+/// ```rust
+/// # use algorithms::graph::{johnson, Distance, GraphBuilder};
+/// let mut g = GraphBuilder::new(3);
+/// g.add_edge(0, 1, 2i64);
+/// g.add_edge(1, 2, -1);
+/// g.add_edge(0, 2, 4);
+/// let paths = johnson(&g.clone().build()).unwrap();
+/// assert_eq!(Distance::Finite(1), paths.dist(0, 2));
+/// assert_eq!(Some(vec![0, 1, 2]), paths.path(0, 2));
+/// assert_eq!(Distance::Unreachable, paths.dist(2, 0));
+///
+/// g.add_edge(2, 0, -2);
+/// assert_eq!(None, johnson(&g.build()));
+/// ```
+pub fn johnson<W>(g: &Graph<W>) -> Option<AllPairs<W>>
+where
+    W: Copy + Ord + Zero + CheckedAdd + Sub<Output = W>,
+{
+    let n = g.len();
+    let h = potentials(g)?;
+    let reweighted = g.map(|u, v, &w| w + h[u] - h[v]);
+
+    let mut dist = Vec::with_capacity(n * n);
+    let mut next = Vec::with_capacity(n * n);
+    for u in 0..n {
+        let paths = dijkstra(&reweighted, u);
+        dist.extend((0..n).map(|v| match paths.dist(v) {
+            Some(d) => Distance::Finite(d - h[u] + h[v]),
+            None => Distance::Unreachable,
+        }));
+
+        // the next hop to v is the next hop to its predecessor, or v itself if that's u
+        let mut row = vec![None; n];
+        row[u] = Some(u);
+        for v in 0..n {
+            if paths.dist(v).is_none() {
+                continue;
+            }
+            let mut chain = vec![];
+            let mut x = v;
+            while row[x].is_none() {
+                chain.push(x);
+                x = paths.pred(x).unwrap();
+            }
+            for &y in chain.iter().rev() {
+                row[y] = if x == u { Some(y) } else { row[x] };
+                x = y;
+            }
+        }
+        next.extend(row);
+    }
+
+    Some(AllPairs::new(n, dist, next))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{floyd_warshall, GraphBuilder};
+
+    #[test]
+    fn generated() {
+        use rand::Rng;
+
+        let mut r = rand_pcg::Pcg32::new(0xcafef00dd15ea5e5, 0xa02bdbf7bb3c0a7);
+        for _ in 0..50 {
+            let n = r.gen_range(1..15);
+            let m = r.gen_range(0..3 * n);
+            // weights w + p[u] - p[v] for w >= 0 give negative edges but no negative cycles
+            let p: Vec<i64> = (0..n).map(|_| r.gen_range(-10..10)).collect();
+            let mut b = GraphBuilder::new(n);
+            for _ in 0..m {
+                let (u, v) = (r.gen_range(0..n), r.gen_range(0..n));
+                b.add_edge(u, v, r.gen_range(0..10) + p[u] - p[v]);
+            }
+            let g = b.build();
+
+            let paths = johnson(&g).unwrap();
+            let expected = floyd_warshall(&g);
+            for u in 0..n {
+                for v in 0..n {
+                    assert_eq!(expected.dist(u, v), paths.dist(u, v));
+                    if let Some(path) = paths.path(u, v) {
+                        assert_eq!(Some(&v), path.last());
+                        let len = path
+                            .windows(2)
+                            .map(|p| {
+                                g.edges(p[0])
+                                    .filter(|&(v, _)| v == p[1])
+                                    .map(|(_, &w)| w)
+                                    .min()
+                                    .unwrap()
+                            })
+                            .sum::<i64>();
+                        assert_eq!(expected.dist(u, v), Distance::Finite(len));
+                    }
+                }
+            }
+        }
+    }
+}
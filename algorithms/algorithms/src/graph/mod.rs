@@ -0,0 +1,260 @@
+//! Graphs on the nodes `0..n` with edges weighted by some `W`, which is `()` for unweighted graphs.
+//!
+//! Graphs are put together in a [`GraphBuilder`], an adjacency list which can be grown one node or
+//! edge at a time, and then frozen into a [`Graph`], which stores all edges in one array sorted by
+//! their source (compressed sparse row) so the neighbors of a node are a contiguous slice.
+
+use std::ops::Range;
+
+mod bellman_ford;
+mod biconnected;
+mod dijkstra;
+mod floyd_warshall;
+mod johnson;
+pub mod mst;
+mod scc;
+mod toposort;
+
+pub use bellman_ford::{bellman_ford, negative_cycle, BellmanFord, Distance};
+pub use biconnected::{biconnectivity, Biconnectivity};
+pub use dijkstra::{dijkstra, dijkstra_to, earliest_arrival, ShortestPaths};
+pub use floyd_warshall::{floyd_warshall, AllPairs};
+pub use johnson::johnson;
+pub use scc::{strongly_connected_components, Condensation};
+pub use toposort::{toposort, toposort_dfs, toposort_smallest};
+
+/// A mutable adjacency list, see [`GraphBuilder::build`].
+#[derive(Debug, Clone)]
+pub struct GraphBuilder<W = ()> {
+    // adj[u] = [(v, edge id, weight)]
+    adj: Vec<Vec<(usize, usize, W)>>,
+    edges: usize,
+}
+
+impl<W> GraphBuilder<W> {
+    pub fn new(len: usize) -> Self {
+        Self {
+            adj: (0..len).map(|_| vec![]).collect(),
+            edges: 0,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.adj.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.adj.is_empty()
+    }
+
+    /// Adds a node without any edges and returns it.
+    pub fn add_node(&mut self) -> usize {
+        self.adj.push(vec![]);
+        self.adj.len() - 1
+    }
+
+    /// Adds a directed edge from `u` to `v` and returns its id. Ids are handed out in order from 0.
+    pub fn add_edge(&mut self, u: usize, v: usize, weight: W) -> usize {
+        assert!(v < self.adj.len(), "edge to a node that doesn't exist");
+        self.adj[u].push((v, self.edges, weight));
+        self.edges += 1;
+        self.edges - 1
+    }
+
+    /// Adds edges in both directions between `u` and `v`, which share the returned id.
+    pub fn add_undirected_edge(&mut self, u: usize, v: usize, weight: W) -> usize
+    where
+        W: Clone,
+    {
+        let id = self.add_edge(u, v, weight.clone());
+        if u != v {
+            self.adj[v].push((u, id, weight));
+        }
+        id
+    }
+
+    /// Freezes the builder into a [`Graph`]. The edges out of every node keep the order in which
+    /// they were added.
+    ///
+    /// # Time complexity
+    /// *O*(*n* + *m*)
+    pub fn build(self) -> Graph<W> {
+        let mut start = Vec::with_capacity(self.adj.len() + 1);
+        start.push(0);
+        for list in &self.adj {
+            start.push(start[start.len() - 1] + list.len());
+        }
+        let m = start[self.adj.len()];
+        let mut targets = Vec::with_capacity(m);
+        let mut ids = Vec::with_capacity(m);
+        let mut weights = Vec::with_capacity(m);
+        for (v, id, w) in self.adj.into_iter().flatten() {
+            targets.push(v);
+            ids.push(id);
+            weights.push(w);
+        }
+        Graph {
+            start,
+            targets,
+            ids,
+            weights,
+            edges: self.edges,
+        }
+    }
+}
+
+/// An immutable graph in compressed sparse row form, built with a [`GraphBuilder`].
+///
+/// # Examples
+/// ```rust
+/// # use algorithms::graph::GraphBuilder;
+/// let mut g = GraphBuilder::new(3);
+/// g.add_edge(0, 1, 5);
+/// g.add_undirected_edge(1, 2, 7);
+/// let g = g.build();
+/// assert_eq!(&[1], g.neighbors(0));
+/// assert_eq!(vec![(2, &7)], g.edges(1).collect::<Vec<_>>());
+/// assert_eq!(vec![(1, 1, &7)], g.reversed().edges_from(2).collect::<Vec<_>>());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Graph<W = ()> {
+    // the edges out of u are at start[u]..start[u + 1]
+    start: Vec<usize>,
+    targets: Vec<usize>,
+    ids: Vec<usize>,
+    weights: Vec<W>,
+    edges: usize,
+}
+
+impl<W> Graph<W> {
+    /// The number of nodes.
+    pub fn len(&self) -> usize {
+        self.start.len() - 1
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The number of edges added to the builder, i.e. undirected edges are counted once.
+    pub fn num_edges(&self) -> usize {
+        self.edges
+    }
+
+    fn range(&self, u: usize) -> Range<usize> {
+        self.start[u]..self.start[u + 1]
+    }
+
+    pub fn degree(&self, u: usize) -> usize {
+        self.start[u + 1] - self.start[u]
+    }
+
+    /// The nodes `u` has edges to, with repetitions if there are parallel edges.
+    pub fn neighbors(&self, u: usize) -> &[usize] {
+        &self.targets[self.range(u)]
+    }
+
+    /// The weights of the edges out of `u`, in the same order as [`Graph::neighbors`].
+    pub fn weights(&self, u: usize) -> &[W] {
+        &self.weights[self.range(u)]
+    }
+
+    /// The ids of the edges out of `u`, in the same order as [`Graph::neighbors`].
+    pub fn edge_ids(&self, u: usize) -> &[usize] {
+        &self.ids[self.range(u)]
+    }
+
+    /// The edges out of `u` as `(v, weight)`.
+    pub fn edges(&self, u: usize) -> impl Iterator<Item = (usize, &W)> + '_ {
+        self.neighbors(u).iter().copied().zip(self.weights(u))
+    }
+
+    /// The edges out of `u` as `(v, id, weight)`.
+    pub fn edges_from(&self, u: usize) -> impl Iterator<Item = (usize, usize, &W)> + '_ {
+        let r = self.range(u);
+        self.targets[r.clone()]
+            .iter()
+            .zip(&self.ids[r.clone()])
+            .zip(&self.weights[r])
+            .map(|((&v, &id), w)| (v, id, w))
+    }
+
+    /// Every edge as `(u, v, weight)`, undirected edges once in each direction.
+    pub fn all_edges(&self) -> impl Iterator<Item = (usize, usize, &W)> + '_ {
+        (0..self.len()).flat_map(move |u| self.edges(u).map(move |(v, w)| (u, v, w)))
+    }
+
+    /// The same graph with every edge `(u, v, w)` given the weight `f(u, v, w)`.
+    pub fn map<V, F: FnMut(usize, usize, &W) -> V>(&self, mut f: F) -> Graph<V> {
+        Graph {
+            start: self.start.clone(),
+            targets: self.targets.clone(),
+            ids: self.ids.clone(),
+            weights: self.all_edges().map(|(u, v, w)| f(u, v, w)).collect(),
+            edges: self.edges,
+        }
+    }
+
+    /// The graph with every edge turned around, keeping the edge ids.
+    pub fn reversed(&self) -> Self
+    where
+        W: Clone,
+    {
+        let mut g = GraphBuilder::new(self.len());
+        for u in 0..self.len() {
+            for (v, id, w) in self.edges_from(u) {
+                g.adj[v].push((u, id, w.clone()));
+            }
+        }
+        g.edges = self.edges;
+        g.build()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn build() {
+        let mut b = GraphBuilder::new(3);
+        b.add_edge(2, 0, 'a');
+        b.add_edge(0, 2, 'b');
+        let u = b.add_node();
+        assert_eq!(2, b.add_undirected_edge(u, 0, 'c'));
+        b.add_edge(0, 0, 'd');
+        assert_eq!(4, b.add_undirected_edge(1, 1, 'e'));
+        let g = b.build();
+
+        assert_eq!(4, g.len());
+        assert_eq!(5, g.num_edges());
+        assert_eq!(&[2, 3, 0], g.neighbors(0));
+        assert_eq!(&['b', 'c', 'd'], g.weights(0));
+        assert_eq!(&[1, 2, 3], g.edge_ids(0));
+        assert_eq!(1, g.degree(1));
+        assert_eq!(1, g.degree(3));
+        assert_eq!(6, g.all_edges().count());
+
+        let r = g.reversed();
+        assert_eq!(5, r.num_edges());
+        assert_eq!(
+            vec![(0, 3, &'d'), (2, 0, &'a'), (3, 2, &'c')],
+            r.edges_from(0).collect::<Vec<_>>()
+        );
+        assert_eq!(&[0], r.neighbors(2));
+        assert_eq!(
+            vec![(0, 2, 'B'), (0, 3, 'C'), (0, 0, 'D')],
+            g.map(|u, v, w| (u, v, w.to_ascii_uppercase()))
+                .weights(0)
+                .to_vec()
+        );
+        let rr = r.reversed();
+        for u in 0..g.len() {
+            let mut edges = rr.edges_from(u).collect::<Vec<_>>();
+            edges.sort();
+            let mut expected = g.edges_from(u).collect::<Vec<_>>();
+            expected.sort();
+            assert_eq!(expected, edges);
+        }
+    }
+}
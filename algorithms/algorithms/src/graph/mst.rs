@@ -0,0 +1,217 @@
+//! Minimum spanning forests of undirected graphs, i.e. graphs built with
+//! [`GraphBuilder::add_undirected_edge`](super::GraphBuilder::add_undirected_edge).
+
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+use super::Graph;
+use crate::num::Zero;
+use crate::UnionFind;
+
+/// A minimum spanning tree of every connected component of a graph.
+///
+/// Components are numbered in the order of their smallest node.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SpanningForest<W> {
+    edges: Vec<(usize, usize, W)>,
+    component: Vec<usize>,
+    weights: Vec<W>,
+}
+
+impl<W: Copy + Zero> SpanningForest<W> {
+    fn new(g: &Graph<W>, edges: Vec<(usize, usize, W)>) -> Self {
+        let mut uf = UnionFind::<Vec<usize>>::new(g.len());
+        for &(u, v, _) in &edges {
+            uf.join(u, v);
+        }
+        let mut index = vec![usize::MAX; g.len()];
+        let mut weights = vec![];
+        let component: Vec<_> = (0..g.len())
+            .map(|v| {
+                let r = uf.root(v);
+                if index[r] == usize::MAX {
+                    index[r] = weights.len();
+                    weights.push(W::ZERO);
+                }
+                index[r]
+            })
+            .collect();
+        for &(u, _, w) in &edges {
+            weights[component[u]] = weights[component[u]] + w;
+        }
+        Self {
+            edges,
+            component,
+            weights,
+        }
+    }
+
+    /// The total weight of all trees.
+    pub fn weight(&self) -> W {
+        self.weights.iter().fold(W::ZERO, |a, &b| a + b)
+    }
+
+    /// The edges of all trees as `(u, v, weight)`.
+    pub fn edges(&self) -> &[(usize, usize, W)] {
+        &self.edges
+    }
+
+    pub fn num_components(&self) -> usize {
+        self.weights.len()
+    }
+
+    /// Whether the graph is connected, i.e. the forest is a single tree.
+    pub fn is_tree(&self) -> bool {
+        self.weights.len() <= 1
+    }
+
+    /// The index of the component `v` is in.
+    pub fn component(&self, v: usize) -> usize {
+        self.component[v]
+    }
+
+    /// The weight of the tree spanning component `c`.
+    pub fn component_weight(&self, c: usize) -> W {
+        self.weights[c]
+    }
+}
+
+/// A minimum spanning forest found by adding the edges in order of weight unless they would form
+/// a cycle. The edges of the result are sorted by weight.
+///
+/// # Time complexity
+/// *O*(*m* log *m*)
+///
+/// # Examples
+/// ```rust
+/// # use algorithms::graph::{mst::kruskal, GraphBuilder};
+/// let mut g = GraphBuilder::new(5);
+/// g.add_undirected_edge(0, 1, 3);
+/// g.add_undirected_edge(1, 2, 1);
+/// g.add_undirected_edge(0, 2, 2);
+/// g.add_undirected_edge(3, 4, 7);
+/// let forest = kruskal(&g.build());
+/// assert_eq!(10, forest.weight());
+/// assert_eq!(&[(1, 2, 1), (0, 2, 2), (3, 4, 7)], forest.edges());
+/// assert_eq!(2, forest.num_components());
+/// assert_eq!(3, forest.component_weight(forest.component(0)));
+/// ```
+pub fn kruskal<W: Copy + Ord + Zero>(g: &Graph<W>) -> SpanningForest<W> {
+    let mut seen = vec![false; g.num_edges()];
+    let mut edges = vec![];
+    for u in 0..g.len() {
+        for (v, id, &w) in g.edges_from(u) {
+            if !seen[id] {
+                seen[id] = true;
+                edges.push((u, v, w));
+            }
+        }
+    }
+    edges.sort_by_key(|&(_, _, w)| w);
+
+    let mut uf = UnionFind::<Vec<usize>>::new(g.len());
+    edges.retain(|&(u, v, _)| uf.join(u, v));
+    SpanningForest::new(g, edges)
+}
+
+/// A minimum spanning forest found by growing a tree from the smallest node not in a tree yet,
+/// always adding the lightest edge leaving it. The edges of the result are in the order they were
+/// added.
+///
+/// # Time complexity
+/// *O*((*n* + *m*) log *m*)
+pub fn prim<W: Copy + Ord + Zero>(g: &Graph<W>) -> SpanningForest<W> {
+    let mut visited = vec![false; g.len()];
+    let mut edges = vec![];
+    let mut heap = BinaryHeap::new();
+    for s in 0..g.len() {
+        if visited[s] {
+            continue;
+        }
+        visited[s] = true;
+        heap.extend(g.edges(s).map(|(v, &w)| Reverse((w, s, v))));
+        while let Some(Reverse((w, u, v))) = heap.pop() {
+            if visited[v] {
+                continue;
+            }
+            visited[v] = true;
+            edges.push((u, v, w));
+            heap.extend(
+                g.edges(v)
+                    .filter(|&(x, _)| !visited[x])
+                    .map(|(x, &w)| Reverse((w, v, x))),
+            );
+        }
+    }
+    SpanningForest::new(g, edges)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::GraphBuilder;
+
+    #[test]
+    fn generated() {
+        use rand::Rng;
+
+        let mut r = rand_pcg::Pcg32::new(0xcafef00dd15ea5e5, 0xa02bdbf7bb3c0a7);
+        for _ in 0..100 {
+            let n = r.gen_range(1..10);
+            let m = r.gen_range(0..2 * n);
+            let mut b = GraphBuilder::new(n);
+            let mut edges = vec![];
+            for _ in 0..m {
+                let (u, v, w) = (r.gen_range(0..n), r.gen_range(0..n), r.gen_range(-5..10i32));
+                b.add_undirected_edge(u, v, w);
+                edges.push((u, v, w));
+            }
+            let g = b.build();
+
+            // every subset of n - components edges without a cycle
+            let mut uf = UnionFind::<Vec<usize>>::new(n);
+            for &(u, v, _) in &edges {
+                uf.join(u, v);
+            }
+            let k = n - uf.num_components();
+            let best = (0..1u32 << m)
+                .filter(|s| s.count_ones() as usize == k)
+                .filter_map(|s| {
+                    let mut uf = UnionFind::<Vec<usize>>::new(n);
+                    let mut sum = 0;
+                    for (i, &(u, v, w)) in edges.iter().enumerate() {
+                        if s >> i & 1 == 1 {
+                            if !uf.join(u, v) {
+                                return None;
+                            }
+                            sum += w;
+                        }
+                    }
+                    Some(sum)
+                })
+                .min()
+                .unwrap();
+
+            for forest in [kruskal(&g), prim(&g)] {
+                assert_eq!(best, forest.weight());
+                assert_eq!(k, forest.edges().len());
+                assert_eq!(uf.num_components(), forest.num_components());
+                assert_eq!(uf.num_components() == 1, forest.is_tree());
+                let mut check = UnionFind::<Vec<usize>>::new(n);
+                for &(u, v, w) in forest.edges() {
+                    assert!(edges.contains(&(u, v, w)) || edges.contains(&(v, u, w)));
+                    assert!(check.join(u, v));
+                }
+                for u in 0..n {
+                    for v in 0..n {
+                        assert_eq!(uf.same(u, v), forest.component(u) == forest.component(v));
+                    }
+                }
+                let total = (0..forest.num_components())
+                    .map(|c| forest.component_weight(c))
+                    .sum::<i32>();
+                assert_eq!(best, total);
+            }
+        }
+    }
+}
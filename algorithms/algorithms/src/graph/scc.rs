@@ -0,0 +1,249 @@
+use super::{Graph, GraphBuilder};
+
+/// The strongly connected components of a directed graph, see [`strongly_connected_components`].
+///
+/// The components are numbered in topological order, i.e. every edge between two components goes
+/// from a lower to a higher number.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Condensation {
+    component: Vec<usize>,
+    dag: Graph,
+}
+
+impl Condensation {
+    pub fn num_components(&self) -> usize {
+        self.dag.len()
+    }
+
+    /// The index of the component `v` is in.
+    pub fn component(&self, v: usize) -> usize {
+        self.component[v]
+    }
+
+    /// The nodes of every component, in order.
+    pub fn members(&self) -> Vec<Vec<usize>> {
+        let mut members = vec![vec![]; self.num_components()];
+        for (v, &c) in self.component.iter().enumerate() {
+            members[c].push(v);
+        }
+        members
+    }
+
+    /// The graph with a node per component and an edge between two components if there is one
+    /// between any of their nodes, without parallel edges.
+    pub fn dag(&self) -> &Graph {
+        &self.dag
+    }
+
+    /// The smallest number of edges that have to be added to make the graph strongly connected,
+    /// i.e. the largest of the number of components without incoming and without outgoing edges.
+    pub fn edges_to_strongly_connect(&self) -> usize {
+        if self.num_components() <= 1 {
+            return 0;
+        }
+        let mut has_in = vec![false; self.num_components()];
+        for (_, v, _) in self.dag.all_edges() {
+            has_in[v] = true;
+        }
+        let sources = has_in.iter().filter(|&&x| !x).count();
+        let sinks = (0..self.num_components())
+            .filter(|&c| self.dag.degree(c) == 0)
+            .count();
+        sources.max(sinks)
+    }
+}
+
+/// Finds the strongly connected components of a graph with Tarjan's algorithm, using an explicit
+/// stack so that long paths can't overflow the call stack.
+///
+/// # Time complexity
+/// *O*(*n* + *m* log *m*), the log for removing parallel edges between components.
+///
+/// # Examples
+/// ```rust
+/// # use algorithms::graph::{strongly_connected_components, GraphBuilder};
+/// let mut g = GraphBuilder::new(5);
+/// g.add_edge(3, 4, ());
+/// g.add_edge(4, 3, ());
+/// g.add_edge(1, 3, ());
+/// g.add_edge(0, 1, ());
+/// g.add_edge(2, 1, ());
+/// let scc = strongly_connected_components(&g.build());
+/// assert_eq!(4, scc.num_components());
+/// assert_eq!(scc.component(3), scc.component(4));
+/// assert!(scc.component(1) < scc.component(3));
+/// assert_eq!(2, scc.edges_to_strongly_connect());
+/// ```
+pub fn strongly_connected_components<W>(g: &Graph<W>) -> Condensation {
+    let n = g.len();
+    let mut index = vec![usize::MAX; n];
+    let mut low = vec![0; n];
+    let mut on_stack = vec![false; n];
+    let mut stack = vec![];
+    let mut component = vec![usize::MAX; n];
+    let mut count = 0;
+    let mut time = 0;
+
+    // (node, how many of its edges have been looked at)
+    let mut calls = vec![];
+    for s in 0..n {
+        if index[s] != usize::MAX {
+            continue;
+        }
+        index[s] = time;
+        low[s] = time;
+        time += 1;
+        stack.push(s);
+        on_stack[s] = true;
+        calls.push((s, 0));
+
+        while let Some(&(u, i)) = calls.last() {
+            if let Some(&v) = g.neighbors(u).get(i) {
+                calls.last_mut().unwrap().1 += 1;
+                if index[v] == usize::MAX {
+                    index[v] = time;
+                    low[v] = time;
+                    time += 1;
+                    stack.push(v);
+                    on_stack[v] = true;
+                    calls.push((v, 0));
+                } else if on_stack[v] {
+                    low[u] = low[u].min(index[v]);
+                }
+                continue;
+            }
+
+            calls.pop();
+            if let Some(&(p, _)) = calls.last() {
+                low[p] = low[p].min(low[u]);
+            }
+            if low[u] == index[u] {
+                loop {
+                    let v = stack.pop().unwrap();
+                    on_stack[v] = false;
+                    component[v] = count;
+                    if v == u {
+                        break;
+                    }
+                }
+                count += 1;
+            }
+        }
+    }
+
+    // Tarjan finds the components in reverse topological order
+    for c in &mut component {
+        *c = count - 1 - *c;
+    }
+
+    let mut edges: Vec<_> = g
+        .all_edges()
+        .map(|(u, v, _)| (component[u], component[v]))
+        .filter(|(a, b)| a != b)
+        .collect();
+    edges.sort_unstable();
+    edges.dedup();
+    let mut dag = GraphBuilder::new(count);
+    for (a, b) in edges {
+        dag.add_edge(a, b, ());
+    }
+
+    Condensation {
+        component,
+        dag: dag.build(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generated() {
+        use rand::Rng;
+
+        let mut r = rand_pcg::Pcg32::new(0xcafef00dd15ea5e5, 0xa02bdbf7bb3c0a7);
+        for _ in 0..100 {
+            let n = r.gen_range(1..20);
+            let m = r.gen_range(0..2 * n);
+            let mut b = GraphBuilder::new(n);
+            for _ in 0..m {
+                b.add_edge(r.gen_range(0..n), r.gen_range(0..n), ());
+            }
+            let g = b.build();
+
+            let mut reach = vec![vec![false; n]; n];
+            for (u, v, _) in g.all_edges() {
+                reach[u][v] = true;
+            }
+            for (u, row) in reach.iter_mut().enumerate() {
+                row[u] = true;
+            }
+            for k in 0..n {
+                for a in 0..n {
+                    for b in 0..n {
+                        reach[a][b] |= reach[a][k] && reach[k][b];
+                    }
+                }
+            }
+
+            let scc = strongly_connected_components(&g);
+            for (u, row) in reach.iter().enumerate() {
+                for (v, &forward) in row.iter().enumerate() {
+                    let same = forward && reach[v][u];
+                    assert_eq!(same, scc.component(u) == scc.component(v));
+                }
+            }
+            for (u, v, _) in g.all_edges() {
+                assert!(scc.component(u) <= scc.component(v));
+            }
+            let members = scc.members();
+            assert_eq!(n, members.iter().map(|c| c.len()).sum());
+            for (u, v, _) in scc.dag().all_edges() {
+                assert!(u < v);
+                assert!(members[u]
+                    .iter()
+                    .any(|&a| g.neighbors(a).iter().any(|&b| members[v].contains(&b))));
+            }
+        }
+    }
+
+    #[test]
+    fn long_path() {
+        let n = 200000;
+        let mut b = GraphBuilder::new(n);
+        for u in 1..n {
+            b.add_edge(u - 1, u, ());
+        }
+        let scc = strongly_connected_components(&b.build());
+        assert_eq!(n, scc.num_components());
+        assert_eq!(1, scc.edges_to_strongly_connect());
+    }
+
+    #[test]
+    fn augmentation() {
+        let mut b = GraphBuilder::new(3);
+        b.add_edge(0, 1, ());
+        b.add_edge(1, 2, ());
+        b.add_edge(2, 0, ());
+        assert_eq!(
+            0,
+            strongly_connected_components(&b.build()).edges_to_strongly_connect()
+        );
+
+        let b = GraphBuilder::<()>::new(4);
+        assert_eq!(
+            4,
+            strongly_connected_components(&b.build()).edges_to_strongly_connect()
+        );
+
+        // two sources, one sink and an isolated node
+        let mut b = GraphBuilder::new(4);
+        b.add_edge(0, 2, ());
+        b.add_edge(1, 2, ());
+        assert_eq!(
+            3,
+            strongly_connected_components(&b.build()).edges_to_strongly_connect()
+        );
+    }
+}
@@ -0,0 +1,234 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, VecDeque};
+
+use super::Graph;
+
+/// A topological order of a graph, i.e. an order of the nodes where every edge goes forward, found
+/// with Kahn's algorithm. If there is none, returns a cycle as the nodes along it, which can be
+/// used to explain why.
+///
+/// # Time complexity
+/// *O*(*n* + *m*)
+///
+/// # Examples
+/// ```rust
+/// # use algorithms::graph::{toposort, GraphBuilder};
+/// let mut g = GraphBuilder::new(3);
+/// g.add_edge(2, 0, ());
+/// g.add_edge(0, 1, ());
+/// assert_eq!(Ok(vec![2, 0, 1]), toposort(&g.clone().build()));
+///
+/// g.add_edge(1, 2, ());
+/// let cycle = toposort(&g.build()).unwrap_err();
+/// assert!([[0, 1, 2], [1, 2, 0], [2, 0, 1]].iter().any(|c| c == &cycle[..]));
+/// ```
+pub fn toposort<W>(g: &Graph<W>) -> Result<Vec<usize>, Vec<usize>> {
+    kahn(g, VecDeque::new())
+}
+
+/// Like [`toposort`], but returns the lexicographically smallest order, by always picking the
+/// smallest node which has no edges into it left.
+///
+/// # Time complexity
+/// *O*(*n* log *n* + *m*)
+pub fn toposort_smallest<W>(g: &Graph<W>) -> Result<Vec<usize>, Vec<usize>> {
+    kahn(g, BinaryHeap::new())
+}
+
+/// Like [`toposort`], but orders the nodes by decreasing finishing time in a depth first search,
+/// which stops at the first cycle it finds.
+///
+/// # Time complexity
+/// *O*(*n* + *m*)
+pub fn toposort_dfs<W>(g: &Graph<W>) -> Result<Vec<usize>, Vec<usize>> {
+    #[derive(Clone, Copy, PartialEq, Eq)]
+    enum State {
+        New,
+        Active,
+        Done,
+    }
+
+    let mut state = vec![State::New; g.len()];
+    let mut order = Vec::with_capacity(g.len());
+    // (node, how many of its edges have been looked at)
+    let mut calls = vec![];
+    for s in 0..g.len() {
+        if state[s] != State::New {
+            continue;
+        }
+        state[s] = State::Active;
+        calls.push((s, 0));
+        while let Some(&(u, i)) = calls.last() {
+            let Some(&v) = g.neighbors(u).get(i) else {
+                state[u] = State::Done;
+                order.push(u);
+                calls.pop();
+                continue;
+            };
+            calls.last_mut().unwrap().1 += 1;
+            match state[v] {
+                State::New => {
+                    state[v] = State::Active;
+                    calls.push((v, 0));
+                }
+                State::Active => {
+                    // the active nodes are the path from s to u, which v is on
+                    let start = calls.iter().position(|&(w, _)| w == v).unwrap();
+                    return Err(calls[start..].iter().map(|&(w, _)| w).collect());
+                }
+                State::Done => {}
+            }
+        }
+    }
+    order.reverse();
+    Ok(order)
+}
+
+// The nodes which currently have no edges into them left.
+trait Frontier {
+    fn push(&mut self, v: usize);
+    fn pop(&mut self) -> Option<usize>;
+}
+
+impl Frontier for VecDeque<usize> {
+    fn push(&mut self, v: usize) {
+        self.push_back(v);
+    }
+    fn pop(&mut self) -> Option<usize> {
+        self.pop_front()
+    }
+}
+
+impl Frontier for BinaryHeap<Reverse<usize>> {
+    fn push(&mut self, v: usize) {
+        BinaryHeap::push(self, Reverse(v));
+    }
+    fn pop(&mut self) -> Option<usize> {
+        BinaryHeap::pop(self).map(|Reverse(v)| v)
+    }
+}
+
+fn kahn<W, F: Frontier>(g: &Graph<W>, mut frontier: F) -> Result<Vec<usize>, Vec<usize>> {
+    let mut indegree = vec![0; g.len()];
+    for (_, v, _) in g.all_edges() {
+        indegree[v] += 1;
+    }
+    for (v, &d) in indegree.iter().enumerate() {
+        if d == 0 {
+            frontier.push(v);
+        }
+    }
+
+    let mut order = Vec::with_capacity(g.len());
+    while let Some(u) = frontier.pop() {
+        order.push(u);
+        for &v in g.neighbors(u) {
+            indegree[v] -= 1;
+            if indegree[v] == 0 {
+                frontier.push(v);
+            }
+        }
+    }
+    if order.len() == g.len() {
+        return Ok(order);
+    }
+
+    // Every node left has an edge into it from another node left, so following those edges
+    // backwards eventually ends up going around a cycle.
+    let mut pred = vec![usize::MAX; g.len()];
+    for (u, v, _) in g.all_edges() {
+        if indegree[u] > 0 && indegree[v] > 0 {
+            pred[v] = u;
+        }
+    }
+    let mut seen = vec![false; g.len()];
+    let mut v = (0..g.len()).find(|&v| indegree[v] > 0).unwrap();
+    while !seen[v] {
+        seen[v] = true;
+        v = pred[v];
+    }
+    let mut cycle = vec![v];
+    let mut u = pred[v];
+    while u != v {
+        cycle.push(u);
+        u = pred[u];
+    }
+    cycle.reverse();
+    Err(cycle)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::GraphBuilder;
+
+    fn permutations(n: usize) -> Vec<Vec<usize>> {
+        if n == 0 {
+            return vec![vec![]];
+        }
+        let mut all = vec![];
+        for p in permutations(n - 1) {
+            for i in 0..n {
+                let mut p = p.clone();
+                p.insert(i, n - 1);
+                all.push(p);
+            }
+        }
+        all
+    }
+
+    #[test]
+    fn generated() {
+        use rand::Rng;
+
+        let mut r = rand_pcg::Pcg32::new(0xcafef00dd15ea5e5, 0xa02bdbf7bb3c0a7);
+        let mut cycles = 0;
+        for round in 0..200 {
+            let n = r.gen_range(1..7);
+            let m = r.gen_range(0..2 * n);
+            let mut b = GraphBuilder::new(n);
+            for _ in 0..m {
+                let (u, v) = (r.gen_range(0..n), r.gen_range(0..n));
+                // make half of the graphs acyclic
+                if round & 1 == 0 || u < v {
+                    b.add_edge(u, v, ());
+                }
+            }
+            let g = b.build();
+
+            let valid = |order: &[usize]| {
+                let mut pos = vec![0; n];
+                for (i, &v) in order.iter().enumerate() {
+                    pos[v] = i;
+                }
+                g.all_edges().all(|(u, v, _)| pos[u] < pos[v])
+            };
+            let smallest = permutations(n).into_iter().filter(|p| valid(p)).min();
+
+            for result in [toposort(&g), toposort_smallest(&g), toposort_dfs(&g)] {
+                match result {
+                    Ok(order) => {
+                        let mut sorted = order.clone();
+                        sorted.sort();
+                        assert_eq!((0..n).collect::<Vec<_>>(), sorted);
+                        assert!(valid(&order));
+                    }
+                    Err(cycle) => {
+                        assert_eq!(None, smallest);
+                        cycles += 1;
+                        let mut sorted = cycle.clone();
+                        sorted.sort();
+                        sorted.dedup();
+                        assert_eq!(cycle.len(), sorted.len());
+                        for i in 0..cycle.len() {
+                            let next = cycle[(i + 1) % cycle.len()];
+                            assert!(g.neighbors(cycle[i]).contains(&next));
+                        }
+                    }
+                }
+            }
+            assert_eq!(smallest, toposort_smallest(&g).ok());
+        }
+        assert!(cycles > 30);
+    }
+}
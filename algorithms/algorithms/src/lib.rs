@@ -1,6 +1,7 @@
 #![feature(test, array_zip)]
 
 mod binary_search;
+mod dynamic_connectivity;
 mod fenwick;
 mod longest_increasing_subsequence;
 mod quicksort;
@@ -8,10 +9,18 @@ mod segtree;
 mod union_find;
 
 pub mod geometry;
+pub mod graph;
+pub mod monoid;
+pub mod num;
+pub mod sat;
 
 pub use binary_search::binary_search;
-pub use fenwick::FenwickTree;
+pub use dynamic_connectivity::DynamicConnectivity;
+pub use fenwick::{FenwickTree, FenwickTree2D, RangeFenwickTree};
 pub use longest_increasing_subsequence::longest_increasing_subsequence;
+pub use monoid::Monoid;
 pub use quicksort::quicksort;
-pub use segtree::SegmentTree;
-pub use union_find::UnionFind;
+pub use segtree::{
+    CompactSegmentTree, LazySegmentTree, PersistentSegmentTree, SegmentTree, SegmentTree2D, Version,
+};
+pub use union_find::{PersistentUnionFind, RollbackUnionFind, UnionFind, WeightedUnionFind};
@@ -0,0 +1,207 @@
+use std::ops::BitXor;
+
+use crate::num::Zero;
+
+/// An associative operation `op` with an identity element, i.e. for all `a`, `b` and `c`:
+/// `op(op(a, b), c) == op(a, op(b, c))` and `op(identity(), a) == a == op(a, identity())`.
+///
+/// The monoids in this module are zero-sized, so e.g. `SegmentTree<i64, Min>` can be named and
+/// stored like any other type. Tuples of monoids act component-wise on tuples of values.
+pub trait Monoid<T> {
+    fn identity(&self) -> T;
+    fn op(&self, a: T, b: T) -> T;
+}
+
+/// Adapter turning an operation closure and a closure returning its identity into a [`Monoid`].
+#[derive(Debug, Clone, Copy)]
+pub struct FnMonoid<F, U>(pub F, pub U);
+
+impl<T, F, U> Monoid<T> for FnMonoid<F, U>
+where
+    F: Fn(T, T) -> T,
+    U: Fn() -> T,
+{
+    fn identity(&self) -> T {
+        (self.1)()
+    }
+    fn op(&self, a: T, b: T) -> T {
+        (self.0)(a, b)
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Sum;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Min;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Max;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Gcd;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Xor;
+
+impl<T: Zero> Monoid<T> for Sum {
+    fn identity(&self) -> T {
+        T::ZERO
+    }
+    fn op(&self, a: T, b: T) -> T {
+        a + b
+    }
+}
+
+impl<T: Zero + BitXor<Output = T>> Monoid<T> for Xor {
+    fn identity(&self) -> T {
+        T::ZERO
+    }
+    fn op(&self, a: T, b: T) -> T {
+        a ^ b
+    }
+}
+
+/// Types with a smallest and a largest value, used as the identities of [`Max`] and [`Min`].
+pub trait Bounded {
+    const MIN: Self;
+    const MAX: Self;
+}
+
+impl<T: Bounded + PartialOrd> Monoid<T> for Min {
+    fn identity(&self) -> T {
+        T::MAX
+    }
+    fn op(&self, a: T, b: T) -> T {
+        if b < a {
+            b
+        } else {
+            a
+        }
+    }
+}
+
+impl<T: Bounded + PartialOrd> Monoid<T> for Max {
+    fn identity(&self) -> T {
+        T::MIN
+    }
+    fn op(&self, a: T, b: T) -> T {
+        if b > a {
+            b
+        } else {
+            a
+        }
+    }
+}
+
+macro_rules! impl_bounded {
+    ($t:ty, $min:expr, $max:expr) => {
+        impl Bounded for $t {
+            const MIN: $t = $min;
+            const MAX: $t = $max;
+        }
+    };
+}
+
+impl_bounded!(u8, u8::MIN, u8::MAX);
+impl_bounded!(u16, u16::MIN, u16::MAX);
+impl_bounded!(u32, u32::MIN, u32::MAX);
+impl_bounded!(u64, u64::MIN, u64::MAX);
+impl_bounded!(u128, u128::MIN, u128::MAX);
+impl_bounded!(usize, usize::MIN, usize::MAX);
+impl_bounded!(i8, i8::MIN, i8::MAX);
+impl_bounded!(i16, i16::MIN, i16::MAX);
+impl_bounded!(i32, i32::MIN, i32::MAX);
+impl_bounded!(i64, i64::MIN, i64::MAX);
+impl_bounded!(i128, i128::MIN, i128::MAX);
+impl_bounded!(isize, isize::MIN, isize::MAX);
+impl_bounded!(f32, f32::NEG_INFINITY, f32::INFINITY);
+impl_bounded!(f64, f64::NEG_INFINITY, f64::INFINITY);
+
+macro_rules! impl_gcd {
+    ($($t:ty),*) => {
+        $(impl Monoid<$t> for Gcd {
+            fn identity(&self) -> $t {
+                <$t>::ZERO
+            }
+            fn op(&self, mut a: $t, mut b: $t) -> $t {
+                while b != 0 {
+                    (a, b) = (b, a % b);
+                }
+                a
+            }
+        })*
+    };
+}
+
+// The gcd of signed values is taken of their unsigned absolute values, since `abs` overflows on
+// `MIN`. The only result that doesn't fit back, 2^(N-1) for `MIN` together with itself or 0,
+// wraps around to `MIN`, whose absolute value it is.
+macro_rules! impl_signed_gcd {
+    ($($t:ty),*) => {
+        $(impl Monoid<$t> for Gcd {
+            fn identity(&self) -> $t {
+                <$t>::ZERO
+            }
+            fn op(&self, a: $t, b: $t) -> $t {
+                Gcd.op(a.unsigned_abs(), b.unsigned_abs()) as $t
+            }
+        })*
+    };
+}
+
+impl_gcd!(u8, u16, u32, u64, u128, usize);
+impl_signed_gcd!(i8, i16, i32, i64, i128, isize);
+
+macro_rules! impl_tuple {
+    ($(($m:ident, $t:ident, $i:tt)),*) => {
+        impl<$($m, $t),*> Monoid<($($t,)*)> for ($($m,)*)
+        where
+            $($m: Monoid<$t>),*
+        {
+            fn identity(&self) -> ($($t,)*) {
+                ($(self.$i.identity(),)*)
+            }
+            fn op(&self, a: ($($t,)*), b: ($($t,)*)) -> ($($t,)*) {
+                ($(self.$i.op(a.$i, b.$i),)*)
+            }
+        }
+    };
+}
+
+impl_tuple!((A, S, 0), (B, T, 1));
+impl_tuple!((A, S, 0), (B, T, 1), (C, U, 2));
+impl_tuple!((A, S, 0), (B, T, 1), (C, U, 2), (D, V, 3));
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn identities() {
+        assert_eq!(5, Sum.op(Sum.identity(), 5));
+        assert_eq!(-3, Min.op(-3, Min.identity()));
+        assert_eq!(2.5, Max.op(Max.identity(), 2.5));
+        assert_eq!(12u32, Gcd.op(Gcd.identity(), 12));
+        assert_eq!(6, Xor.op(6, Xor.identity()));
+    }
+
+    #[test]
+    fn gcd() {
+        assert_eq!(6, Gcd.op(12, 18));
+        assert_eq!(6, Gcd.op(-12, 18));
+        assert_eq!(1u64, Gcd.op(7, 9));
+        assert_eq!(2, Gcd.op(i64::MIN, 6));
+        assert_eq!(1 << 62, Gcd.op(-(1 << 62), i64::MIN));
+        assert_eq!(i64::MIN, Gcd.op(i64::MIN, Gcd.identity()));
+    }
+
+    #[test]
+    fn tuples() {
+        let m = (Sum, Min, Max);
+        let x = [(1, 4, 4), (2, 1, 1), (3, 8, 8)]
+            .into_iter()
+            .fold(m.identity(), |acc, x| m.op(acc, x));
+        assert_eq!((6, 1, 8), x);
+    }
+}
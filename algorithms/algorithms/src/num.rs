@@ -0,0 +1,132 @@
+use std::ops::{Add, Mul, Neg, Sub};
+
+/// Types with an additive identity.
+pub trait Zero: Sized + Copy + Add<Output = Self> {
+    const ZERO: Self;
+}
+
+/// Types with a multiplicative identity.
+pub trait One: Sized + Copy + Mul<Output = Self> {
+    const ONE: Self;
+}
+
+/// Types where a sum minus one of its parts gives back the other parts, e.g. all integers. Unlike
+/// in a group, `a - b` is only required to work when `a` is a sum containing `b`, so the unsigned
+/// integers are included. This is what fenwick trees need to turn prefix sums into range sums.
+pub trait Cancellative: Zero + Sub<Output = Self> {}
+
+/// An additive group, i.e. every element has a negation, so differences can be taken either way.
+/// This leaves out the unsigned integers.
+pub trait AddGroup: Cancellative + Neg<Output = Self> {}
+
+/// A ring, e.g. the integers, the floats or the integers modulo some number.
+pub trait Ring: AddGroup + One {}
+
+/// Addition which reports overflow, used e.g. to sum up path lengths without wrapping around.
+pub trait CheckedAdd: Sized + Add<Output = Self> {
+    fn checked_add(self, rhs: Self) -> Option<Self>;
+}
+
+impl<T: Zero + Sub<Output = T>> Cancellative for T {}
+impl<T: Cancellative + Neg<Output = T>> AddGroup for T {}
+impl<T: AddGroup + One> Ring for T {}
+
+macro_rules! impl_num {
+    ($t:ty, $z:literal, $o:literal) => {
+        impl Zero for $t {
+            const ZERO: $t = $z;
+        }
+        impl One for $t {
+            const ONE: $t = $o;
+        }
+    };
+}
+
+impl_num!(u8, 0, 1);
+impl_num!(u16, 0, 1);
+impl_num!(u32, 0, 1);
+impl_num!(u64, 0, 1);
+impl_num!(u128, 0, 1);
+impl_num!(usize, 0, 1);
+impl_num!(i8, 0, 1);
+impl_num!(i16, 0, 1);
+impl_num!(i32, 0, 1);
+impl_num!(i64, 0, 1);
+impl_num!(i128, 0, 1);
+impl_num!(isize, 0, 1);
+impl_num!(f32, 0., 1.);
+impl_num!(f64, 0., 1.);
+
+macro_rules! impl_checked_add {
+    ($($t:ty),*) => {
+        $(impl CheckedAdd for $t {
+            fn checked_add(self, rhs: $t) -> Option<$t> {
+                <$t>::checked_add(self, rhs)
+            }
+        })*
+    };
+}
+
+impl_checked_add!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::monoid::Sum;
+    use crate::{FenwickTree, SegmentTree};
+
+    // unordered, so only the group operations are available
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    struct Mod7(u8);
+
+    impl Add for Mod7 {
+        type Output = Self;
+        fn add(self, rhs: Self) -> Self {
+            Self((self.0 + rhs.0) % 7)
+        }
+    }
+
+    impl Sub for Mod7 {
+        type Output = Self;
+        fn sub(self, rhs: Self) -> Self {
+            Self((self.0 + 7 - rhs.0) % 7)
+        }
+    }
+
+    impl Neg for Mod7 {
+        type Output = Self;
+        fn neg(self) -> Self {
+            Self((7 - self.0) % 7)
+        }
+    }
+
+    impl Mul for Mod7 {
+        type Output = Self;
+        fn mul(self, rhs: Self) -> Self {
+            Self(self.0 * rhs.0 % 7)
+        }
+    }
+
+    impl Zero for Mod7 {
+        const ZERO: Self = Self(0);
+    }
+
+    impl One for Mod7 {
+        const ONE: Self = Self(1);
+    }
+
+    #[test]
+    fn modular() {
+        let xs = [5, 6, 3, 4].map(Mod7);
+
+        let mut fw = FenwickTree::<Vec<Mod7>>::from_slice(&xs);
+        assert_eq!(Mod7(4), fw.prefix_sum(4));
+        assert_eq!(Mod7(2), fw.range_sum(1..3));
+        fw.set(1, Mod7::ONE);
+        assert_eq!(Mod7(6), fw.range_sum(0..4));
+
+        let st = SegmentTree::from_monoid(xs.into_iter(), Sum);
+        assert_eq!(Mod7(4), st.query(0..4));
+        assert_eq!(Mod7(0), st.query(2..4));
+    }
+}
@@ -0,0 +1,293 @@
+//! Satisfiability of boolean formulas.
+
+use std::ops::Not;
+
+use crate::graph::{strongly_connected_components, GraphBuilder};
+
+/// A variable or its negation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Lit(usize);
+
+impl Lit {
+    /// The literal which is true when `var` is.
+    pub fn pos(var: usize) -> Self {
+        Self(var * 2)
+    }
+
+    /// The literal which is true when `var` is false.
+    pub fn neg(var: usize) -> Self {
+        Self(var * 2 + 1)
+    }
+
+    pub fn var(self) -> usize {
+        self.0 / 2
+    }
+
+    pub fn is_neg(self) -> bool {
+        self.0 & 1 == 1
+    }
+}
+
+impl Not for Lit {
+    type Output = Self;
+    fn not(self) -> Self {
+        Self(self.0 ^ 1)
+    }
+}
+
+/// A formula in conjunctive normal form where every clause has (at most) two literals.
+///
+/// Every clause `a || b` is turned into the implications `!a -> b` and `!b -> a`. The formula is
+/// satisfiable exactly when no variable and its negation imply each other, i.e. are in the same
+/// strongly connected component of the implications, whose topological order also gives an
+/// assignment.
+///
+/// # Examples
+/// ```rust
+/// # use algorithms::sat::{Lit, TwoSat};
+/// let mut sat = TwoSat::new(3);
+/// sat.add_clause(Lit::pos(0), Lit::neg(1));
+/// sat.implies(Lit::pos(0), Lit::neg(2));
+/// sat.xor(Lit::pos(1), Lit::pos(2));
+/// sat.set(Lit::pos(2));
+/// assert_eq!(Some(vec![false, false, true]), sat.solve());
+///
+/// sat.set(Lit::pos(0));
+/// assert_eq!(None, sat.solve());
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct TwoSat {
+    vars: usize,
+    // the helper variables are numbered down from HELPER, so they never collide with the variables
+    // added later and can be left out of the solution
+    helpers: usize,
+    clauses: Vec<(Lit, Lit)>,
+}
+
+const HELPER: usize = usize::MAX / 2 - 1;
+
+impl TwoSat {
+    pub fn new(vars: usize) -> Self {
+        Self {
+            vars,
+            helpers: 0,
+            clauses: vec![],
+        }
+    }
+
+    /// The number of variables, not counting the helpers added by [`TwoSat::at_most_one`].
+    pub fn len(&self) -> usize {
+        self.vars
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.vars == 0
+    }
+
+    /// Adds a variable and returns it.
+    pub fn add_var(&mut self) -> usize {
+        self.vars += 1;
+        self.vars - 1
+    }
+
+    fn add_helper(&mut self) -> usize {
+        self.helpers += 1;
+        HELPER + 1 - self.helpers
+    }
+
+    /// The index of `var` among all variables, with the helpers after the caller's variables.
+    fn index(&self, var: usize) -> Option<usize> {
+        if var < self.vars {
+            Some(var)
+        } else if var <= HELPER && HELPER - var < self.helpers {
+            Some(self.vars + HELPER - var)
+        } else {
+            None
+        }
+    }
+
+    /// The node of `a` in the implication graph.
+    fn node(&self, a: Lit) -> usize {
+        self.index(a.var()).unwrap() * 2 + a.is_neg() as usize
+    }
+
+    /// Requires `a || b`.
+    pub fn add_clause(&mut self, a: Lit, b: Lit) {
+        assert!(
+            self.index(a.var()).is_some() && self.index(b.var()).is_some(),
+            "unknown variable"
+        );
+        self.clauses.push((a, b));
+    }
+
+    /// Requires `a`.
+    pub fn set(&mut self, a: Lit) {
+        self.add_clause(a, a);
+    }
+
+    /// Requires that `b` is true if `a` is.
+    pub fn implies(&mut self, a: Lit, b: Lit) {
+        self.add_clause(!a, b);
+    }
+
+    /// Requires that exactly one of `a` and `b` is true.
+    pub fn xor(&mut self, a: Lit, b: Lit) {
+        self.add_clause(a, b);
+        self.add_clause(!a, !b);
+    }
+
+    /// Requires that `a` and `b` are both true or both false.
+    pub fn equal(&mut self, a: Lit, b: Lit) {
+        self.xor(a, !b);
+    }
+
+    /// Requires that at most one of `lits` is true. Adds a hidden helper variable for every literal,
+    /// which is true if any literal up to and including it is, to get away with a linear number of
+    /// clauses.
+    pub fn at_most_one(&mut self, lits: &[Lit]) {
+        let mut prev: Option<Lit> = None;
+        for &l in lits {
+            let any = Lit::pos(self.add_helper());
+            self.implies(l, any);
+            if let Some(p) = prev {
+                self.implies(p, any);
+                self.implies(p, !l);
+            }
+            prev = Some(any);
+        }
+    }
+
+    /// A value for every variable which satisfies all clauses, or `None` if there is none.
+    ///
+    /// # Time complexity
+    /// *O*(*n* + *m* log *m*) for *n* variables and *m* clauses.
+    pub fn solve(&self) -> Option<Vec<bool>> {
+        let mut g = GraphBuilder::new((self.vars + self.helpers) * 2);
+        for &(a, b) in &self.clauses {
+            g.add_edge(self.node(!a), self.node(b), ());
+            g.add_edge(self.node(!b), self.node(a), ());
+        }
+        let scc = strongly_connected_components(&g.build());
+        // every variable has to be consistent, but only the caller's are returned
+        let consistent = (self.vars..self.vars + self.helpers)
+            .all(|v| scc.component(v * 2) != scc.component(v * 2 + 1));
+        if !consistent {
+            return None;
+        }
+        (0..self.vars)
+            .map(|v| {
+                let (pos, neg) = (scc.component(v * 2), scc.component(v * 2 + 1));
+                // pick the literal that comes later in topological order, so it can't imply the
+                // other one
+                (pos != neg).then_some(pos > neg)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn satisfies(clauses: &[(Lit, Lit)], values: &[bool]) -> bool {
+        let value = |l: Lit| values[l.var()] != l.is_neg();
+        clauses.iter().all(|&(a, b)| value(a) || value(b))
+    }
+
+    #[test]
+    fn generated() {
+        use rand::Rng;
+
+        let mut r = rand_pcg::Pcg32::new(0xcafef00dd15ea5e5, 0xa02bdbf7bb3c0a7);
+        let mut satisfiable = 0;
+        for _ in 0..300 {
+            let n = r.gen_range(1..8);
+            let mut sat = TwoSat::new(n);
+            let mut lit = || {
+                let v = r.gen_range(0..n);
+                if r.gen() {
+                    Lit::pos(v)
+                } else {
+                    Lit::neg(v)
+                }
+            };
+            let clauses: Vec<_> = (0..n + 1).map(|_| (lit(), lit())).collect();
+            for &(a, b) in &clauses {
+                sat.add_clause(a, b);
+            }
+
+            let any = (0..1u32 << n).any(|s| {
+                let values: Vec<_> = (0..n).map(|v| s >> v & 1 == 1).collect();
+                satisfies(&clauses, &values)
+            });
+            match sat.solve() {
+                Some(values) => {
+                    satisfiable += 1;
+                    assert!(satisfies(&clauses, &values));
+                }
+                None => assert!(!any),
+            }
+        }
+        assert!(satisfiable > 50);
+    }
+
+    #[test]
+    fn at_most_one() {
+        for n in 1..6 {
+            for s in 0..1u32 << n {
+                // require the variables in s, and at most one of all of them
+                let mut sat = TwoSat::new(n);
+                let lits: Vec<_> = (0..n).map(Lit::pos).collect();
+                sat.at_most_one(&lits);
+                assert_eq!(n, sat.len());
+                for v in (0..n).filter(|v| s >> v & 1 == 1) {
+                    sat.set(Lit::pos(v));
+                }
+                let result = sat.solve();
+                assert_eq!(s.count_ones() <= 1, result.is_some());
+                if let Some(values) = result {
+                    assert_eq!(n, values.len());
+                    assert!(values.iter().filter(|&&x| x).count() <= 1);
+                }
+
+                // and with negated literals, at most one false
+                let mut sat = TwoSat::new(n);
+                let lits: Vec<_> = (0..n).map(Lit::neg).collect();
+                sat.at_most_one(&lits);
+                for v in (0..n).filter(|v| s >> v & 1 == 1) {
+                    sat.set(Lit::neg(v));
+                }
+                assert_eq!(s.count_ones() <= 1, sat.solve().is_some());
+            }
+        }
+    }
+
+    #[test]
+    fn helpers() {
+        let mut sat = TwoSat::new(2);
+        sat.equal(Lit::pos(0), Lit::neg(1));
+        sat.set(Lit::neg(1));
+        assert_eq!(Some(vec![true, false]), sat.solve());
+        assert_eq!(!Lit::neg(3), Lit::pos(3));
+        assert_eq!(3, Lit::neg(3).var());
+        assert!(Lit::neg(3).is_neg());
+        sat.xor(Lit::pos(0), Lit::pos(1));
+        assert!(sat.solve().is_some());
+        sat.xor(Lit::pos(0), Lit::neg(1));
+        assert!(sat.solve().is_none());
+    }
+
+    #[test]
+    fn helpers_hidden() {
+        let mut sat = TwoSat::new(2);
+        sat.at_most_one(&[Lit::pos(0), Lit::pos(1)]);
+        let v = sat.add_var();
+        assert_eq!(2, v);
+        assert_eq!(3, sat.len());
+        sat.set(Lit::pos(v));
+        sat.set(Lit::pos(0));
+        assert_eq!(Some(vec![true, false, true]), sat.solve());
+        sat.at_most_one(&[Lit::pos(0), Lit::pos(v)]);
+        assert_eq!(None, sat.solve());
+    }
+}
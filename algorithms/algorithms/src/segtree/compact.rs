@@ -0,0 +1,282 @@
+use std::ops::Range;
+
+use super::Side;
+use crate::monoid::{FnMonoid, Monoid};
+
+/// A segment tree using exactly `2 * len` slots, with the leaves stored at `len..2 * len` and node
+/// `i` combining nodes `2 * i` and `2 * i + 1`. Unlike [`SegmentTree`](super::SegmentTree), the
+/// length isn't rounded up to a power of two and queries are answered without recursion.
+///
+/// When `len` isn't a power of two, some nodes near the root combine the end of the leaves with
+/// their start, so searches start from the nodes a query would use, which are all proper ranges,
+/// and only descend from there.
+#[derive(Debug, Clone)]
+pub struct CompactSegmentTree<T, M> {
+    len: usize,
+    a: Vec<T>,
+    m: M,
+}
+
+impl<T, F, U> CompactSegmentTree<T, FnMonoid<F, U>>
+where
+    T: Copy,
+    F: Fn(T, T) -> T,
+    U: Fn() -> T,
+{
+    pub fn new(len: usize, f: F, unit: U) -> Self {
+        Self::with_monoid(len, FnMonoid(f, unit))
+    }
+    pub fn from<I: ExactSizeIterator<Item = T>>(xs: I, f: F, unit: U) -> Self {
+        Self::from_monoid(xs, FnMonoid(f, unit))
+    }
+}
+
+impl<T, M> CompactSegmentTree<T, M>
+where
+    T: Copy,
+    M: Monoid<T>,
+{
+    pub fn with_monoid(len: usize, m: M) -> Self {
+        Self {
+            len,
+            a: vec![m.identity(); len * 2],
+            m,
+        }
+    }
+    pub fn from_monoid<I: ExactSizeIterator<Item = T>>(xs: I, m: M) -> Self {
+        let mut s = Self::with_monoid(xs.len(), m);
+        for (v, x) in s.a[s.len..].iter_mut().zip(xs) {
+            *v = x;
+        }
+        s.update_all();
+        s
+    }
+    pub fn set(&mut self, index: usize, value: T) {
+        let mut i = self.len + index;
+        self.a[i] = value;
+        while i > 1 {
+            i /= 2;
+            self.a[i] = self.m.op(self.a[i * 2], self.a[i * 2 + 1]);
+        }
+    }
+    pub fn query(&self, r: Range<usize>) -> T {
+        let mut l = r.start + self.len;
+        let mut r = r.end + self.len;
+        let mut left = self.m.identity();
+        let mut right = self.m.identity();
+        while l < r {
+            if l & 1 == 1 {
+                left = self.m.op(left, self.a[l]);
+                l += 1;
+            }
+            if r & 1 == 1 {
+                r -= 1;
+                right = self.m.op(self.a[r], right);
+            }
+            l /= 2;
+            r /= 2;
+        }
+        self.m.op(left, right)
+    }
+    /// The nodes which together cover `r`, from left to right.
+    fn cover(&self, r: Range<usize>) -> impl DoubleEndedIterator<Item = usize> {
+        let mut l = r.start + self.len;
+        let mut r = r.end + self.len;
+        let (mut left, mut right) = (vec![], vec![]);
+        while l < r {
+            if l & 1 == 1 {
+                left.push(l);
+                l += 1;
+            }
+            if r & 1 == 1 {
+                r -= 1;
+                right.push(r);
+            }
+            l /= 2;
+            r /= 2;
+        }
+        left.into_iter().chain(right.into_iter().rev())
+    }
+    /// Like [`SegmentTree::prefer_when`](super::SegmentTree::prefer_when), finds an element for
+    /// which `p` holds by descending into the first node on the preferred side that satisfies it.
+    pub fn prefer_when(&self, side: Side, p: impl Fn(T) -> bool) -> Option<usize> {
+        let mut cover = self.cover(0..self.len);
+        let mut i = if side == Side::Left {
+            cover.find(|&i| p(self.a[i]))
+        } else {
+            cover.rfind(|&i| p(self.a[i]))
+        }?;
+        while i < self.len {
+            let (l, r) = (i * 2, i * 2 + 1);
+            let (prefered, otherwise) = if side == Side::Left { (l, r) } else { (r, l) };
+            if p(self.a[prefered]) {
+                i = prefered;
+            } else if p(self.a[otherwise]) {
+                i = otherwise;
+            } else {
+                return None;
+            }
+        }
+        Some(i - self.len)
+    }
+    /// Given a start `l` and a predicate `p` where `p(identity)` holds and
+    /// `p(query(l..j))` implies `p(query(l..i))` for all `i <= j`,
+    /// returns the largest `r` such that `p(query(l..r))` is true.
+    ///
+    /// # Time complexity
+    /// *O*(log *n*) calls to `p` and the monoid operation.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use algorithms::{monoid::Max, CompactSegmentTree};
+    /// let st = CompactSegmentTree::from_monoid([9, 1, 3, 4, 7, 2].into_iter(), Max);
+    /// assert_eq!(4, st.max_right(2, |x| x < 5));
+    /// ```
+    pub fn max_right(&self, l: usize, p: impl Fn(T) -> bool) -> usize {
+        assert!(l <= self.len);
+        let mut acc = self.m.identity();
+        for mut i in self.cover(l..self.len) {
+            let next = self.m.op(acc, self.a[i]);
+            if p(next) {
+                acc = next;
+                continue;
+            }
+            while i < self.len {
+                i *= 2;
+                let next = self.m.op(acc, self.a[i]);
+                if p(next) {
+                    acc = next;
+                    i += 1;
+                }
+            }
+            return i - self.len;
+        }
+        self.len
+    }
+    /// Given an end `r` and a predicate `p` where `p(identity)` holds and
+    /// `p(query(i..r))` implies `p(query(j..r))` for all `i <= j`,
+    /// returns the smallest `l` such that `p(query(l..r))` is true.
+    ///
+    /// # Time complexity
+    /// *O*(log *n*) calls to `p` and the monoid operation.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use algorithms::{monoid::Max, CompactSegmentTree};
+    /// let st = CompactSegmentTree::from_monoid([9, 1, 6, 4, 7, 2].into_iter(), Max);
+    /// assert_eq!(3, st.min_left(4, |x| x < 5));
+    /// ```
+    pub fn min_left(&self, r: usize, p: impl Fn(T) -> bool) -> usize {
+        assert!(r <= self.len);
+        let mut acc = self.m.identity();
+        for mut i in self.cover(0..r).rev() {
+            let next = self.m.op(self.a[i], acc);
+            if p(next) {
+                acc = next;
+                continue;
+            }
+            while i < self.len {
+                i = i * 2 + 1;
+                let next = self.m.op(self.a[i], acc);
+                if p(next) {
+                    acc = next;
+                    i -= 1;
+                }
+            }
+            return i + 1 - self.len;
+        }
+        0
+    }
+    pub fn change_many(&mut self, func: impl FnOnce(&mut [T])) {
+        func(&mut self.a[self.len..]);
+        self.update_all();
+    }
+    fn update_all(&mut self) {
+        for i in (1..self.len).rev() {
+            self.a[i] = self.m.op(self.a[i * 2], self.a[i * 2 + 1]);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sums() {
+        let xs = [7, 4, 5, 5, 0, 1, 4, 3, 9];
+        let mut st = CompactSegmentTree::new(xs.len(), |a, b| a + b, || 0);
+        for (i, &x) in xs.iter().enumerate() {
+            st.set(i, x);
+        }
+        assert_eq!(2 * xs.len(), st.a.len());
+        for i in 0..xs.len() {
+            for j in i..=xs.len() {
+                assert_eq!(xs[i..j].iter().sum::<i32>(), st.query(i..j), "{}..{}", i, j);
+            }
+        }
+    }
+
+    #[test]
+    fn non_commutative() {
+        // Composition of the affine maps x -> a * x + b, applying the left one first.
+        let compose = |(a, b): (i64, i64), (c, d): (i64, i64)| (a * c % 1009, (b * c + d) % 1009);
+        let xs: Vec<(i64, i64)> = (0..11).map(|i| (i * 7 % 5 + 1, i * 3 % 7)).collect();
+        let st = CompactSegmentTree::from(xs.iter().copied(), compose, || (1, 0));
+        for i in 0..xs.len() {
+            for j in i..=xs.len() {
+                let expected = xs[i..j].iter().fold((1, 0), |acc, &x| compose(acc, x));
+                assert_eq!(expected, st.query(i..j), "{}..{}", i, j);
+            }
+        }
+    }
+
+    #[test]
+    fn prefer_when() {
+        for len in 1..20 {
+            let xs: Vec<usize> = (0..len).map(|i| (i * 7 + 3) % 5).collect();
+            let st = CompactSegmentTree::from(xs.iter().copied(), |a, b| a.min(b), || usize::MAX);
+            for limit in 0..6 {
+                let first = xs.iter().position(|&x| x <= limit);
+                let last = xs.iter().rposition(|&x| x <= limit);
+                assert_eq!(first, st.prefer_when(Side::Left, |x| x <= limit));
+                assert_eq!(last, st.prefer_when(Side::Right, |x| x <= limit));
+            }
+        }
+    }
+
+    #[test]
+    fn max_right_min_left() {
+        for len in 0..20 {
+            let xs: Vec<i32> = (0..len).map(|i| (i * 5 + 2) % 7).collect();
+            let st = CompactSegmentTree::from(xs.iter().copied(), |a, b| a + b, || 0);
+            for i in 0..=len as usize {
+                for limit in 0..40 {
+                    let r = (i..=xs.len())
+                        .take_while(|&j| xs[i..j].iter().sum::<i32>() <= limit)
+                        .last()
+                        .unwrap();
+                    assert_eq!(r, st.max_right(i, |x| x <= limit), "{} {}", i, limit);
+                    let l = (0..=i)
+                        .rev()
+                        .take_while(|&j| xs[j..i].iter().sum::<i32>() <= limit)
+                        .last()
+                        .unwrap();
+                    assert_eq!(l, st.min_left(i, |x| x <= limit), "{} {}", i, limit);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn change_many() {
+        let mut st = CompactSegmentTree::from([1, 2, 3, 8, 4].into_iter(), |a, b| a.max(b), || 0);
+        assert_eq!(8, st.query(0..5));
+        st.change_many(|xs| {
+            xs[0] = 5;
+            xs[3] = 2;
+        });
+        assert_eq!(5, st.query(0..5));
+        assert_eq!(4, st.query(2..5));
+    }
+}
@@ -0,0 +1,285 @@
+use std::ops::{Index, IndexMut, Range};
+
+use crate::monoid::{FnMonoid, Monoid};
+
+/// A two dimensional segment tree over a commutative monoid `M`, using the same layout as
+/// [`CompactSegmentTree`](super::CompactSegmentTree) in both dimensions.
+///
+/// The storage is named by the grid it holds, e.g. `SegmentTree2D<Vec<Vec<i64>>, Min>` or, for a 3
+/// by 4 grid, `SegmentTree2D<[[i64; 4]; 3], Min>`.
+///
+/// # Examples
+/// ```rust
+/// # use algorithms::SegmentTree2D;
+/// let grid = [[0; 4], [5, 1, 2, 6], [0; 4]];
+/// let mut st = SegmentTree2D::<[[i32; 4]; 3], _>::from(grid, i32::max, || 0);
+/// st.set(2, 0, 3);
+/// assert_eq!(5, st.query(1..3, 0..2));
+/// assert_eq!(3, st.query(2..3, 0..4));
+/// ```
+#[derive(Debug, Clone)]
+pub struct SegmentTree2D<A, M> {
+    // The table of 2 * height rows of 2 * width nodes, split into four grid sized quadrants:
+    // q[1][1] holds the elements and q[0][0] the nodes covering both rows and columns.
+    q: [[A; 2]; 2],
+    m: M,
+}
+
+impl<T, F, U> SegmentTree2D<Vec<Vec<T>>, FnMonoid<F, U>>
+where
+    T: Copy,
+    F: Fn(T, T) -> T,
+    U: Fn() -> T,
+{
+    pub fn new(height: usize, width: usize, f: F, unit: U) -> Self {
+        Self::with_monoid(height, width, FnMonoid(f, unit))
+    }
+    pub fn from(grid: Vec<Vec<T>>, f: F, unit: U) -> Self {
+        Self::from_monoid(grid, FnMonoid(f, unit))
+    }
+}
+
+impl<T: Copy, M: Monoid<T>> SegmentTree2D<Vec<Vec<T>>, M> {
+    pub fn with_monoid(height: usize, width: usize, m: M) -> Self {
+        let nodes = vec![vec![m.identity(); width]; height];
+        Self {
+            q: [[nodes.clone(), nodes.clone()], [nodes.clone(), nodes]],
+            m,
+        }
+    }
+    /// Builds a tree containing the rows of `grid` in *O*(*nm*).
+    pub fn from_monoid(grid: Vec<Vec<T>>, m: M) -> Self {
+        let width = grid.first().map_or(0, |r| r.len());
+        assert!(
+            grid.iter().all(|r| r.len() == width),
+            "the rows differ in length"
+        );
+        let nodes = vec![vec![m.identity(); width]; grid.len()];
+        let mut s = Self {
+            q: [[nodes.clone(), nodes.clone()], [nodes, grid]],
+            m,
+        };
+        s.update_all();
+        s
+    }
+}
+
+impl<T, F, U, const W: usize, const H: usize> SegmentTree2D<[[T; W]; H], FnMonoid<F, U>>
+where
+    T: Copy,
+    F: Fn(T, T) -> T,
+    U: Fn() -> T,
+{
+    pub fn new(f: F, unit: U) -> Self {
+        Self::with_monoid(FnMonoid(f, unit))
+    }
+    pub fn from(grid: [[T; W]; H], f: F, unit: U) -> Self {
+        Self::from_monoid(grid, FnMonoid(f, unit))
+    }
+}
+
+impl<T: Copy, M: Monoid<T> + Default, const W: usize, const H: usize> Default
+    for SegmentTree2D<[[T; W]; H], M>
+{
+    fn default() -> Self {
+        Self::with_monoid(M::default())
+    }
+}
+
+impl<T: Copy, M: Monoid<T>, const W: usize, const H: usize> SegmentTree2D<[[T; W]; H], M> {
+    pub fn with_monoid(m: M) -> Self {
+        Self {
+            q: [[[[m.identity(); W]; H]; 2]; 2],
+            m,
+        }
+    }
+    /// Builds a tree containing `grid` in *O*(*nm*).
+    pub fn from_monoid(grid: [[T; W]; H], m: M) -> Self {
+        let nodes = [[m.identity(); W]; H];
+        let mut s = Self {
+            q: [[nodes, nodes], [nodes, grid]],
+            m,
+        };
+        s.update_all();
+        s
+    }
+}
+
+/// Which half of `0..2 * len` node `i` is in, and its index within that half.
+fn half(i: usize, len: usize) -> (usize, usize) {
+    if i < len {
+        (0, i)
+    } else {
+        (1, i - len)
+    }
+}
+
+impl<A, R, T, M> SegmentTree2D<A, M>
+where
+    A: Index<usize, Output = R>,
+    A: IndexMut<usize>,
+    A: AsRef<[R]>, // for .len()
+    R: Index<usize, Output = T>,
+    R: IndexMut<usize>,
+    R: AsRef<[T]>,
+    T: Copy,
+    M: Monoid<T>,
+{
+    pub fn height(&self) -> usize {
+        self.q[0][0].as_ref().len()
+    }
+    pub fn width(&self) -> usize {
+        self.q[0][0]
+            .as_ref()
+            .first()
+            .map_or(0, |r| r.as_ref().len())
+    }
+    fn node(&self, r: usize, c: usize) -> T {
+        let ((qr, r), (qc, c)) = (half(r, self.height()), half(c, self.width()));
+        self.q[qr][qc][r][c]
+    }
+    fn set_node(&mut self, r: usize, c: usize, value: T) {
+        let ((qr, r), (qc, c)) = (half(r, self.height()), half(c, self.width()));
+        self.q[qr][qc][r][c] = value;
+    }
+    fn update_all(&mut self) {
+        let (h, w) = (self.height(), self.width());
+        // the rows below r are done before r, and element rows only need their own columns
+        for r in (1..h * 2).rev() {
+            let cols = if r < h { 1..w * 2 } else { 1..w };
+            for c in cols.rev() {
+                let v = if r < h {
+                    self.m.op(self.node(r * 2, c), self.node(r * 2 + 1, c))
+                } else {
+                    self.m.op(self.node(r, c * 2), self.node(r, c * 2 + 1))
+                };
+                self.set_node(r, c, v);
+            }
+        }
+    }
+    pub fn set(&mut self, i: usize, j: usize, value: T) {
+        let (h, w) = (self.height(), self.width());
+        let mut r = i + h;
+        self.set_node(r, j + w, value);
+        let mut c = j + w;
+        while c > 1 {
+            c /= 2;
+            let v = self.m.op(self.node(r, c * 2), self.node(r, c * 2 + 1));
+            self.set_node(r, c, v);
+        }
+        while r > 1 {
+            r /= 2;
+            let mut c = j + w;
+            loop {
+                let v = self.m.op(self.node(r * 2, c), self.node(r * 2 + 1, c));
+                self.set_node(r, c, v);
+                if c <= 1 {
+                    break;
+                }
+                c /= 2;
+            }
+        }
+    }
+    fn query_row(&self, r: usize, cols: &Range<usize>) -> T {
+        let w = self.width();
+        let mut sum = self.m.identity();
+        let (mut l, mut e) = (cols.start + w, cols.end + w);
+        while l < e {
+            if l & 1 == 1 {
+                sum = self.m.op(sum, self.node(r, l));
+                l += 1;
+            }
+            if e & 1 == 1 {
+                e -= 1;
+                sum = self.m.op(sum, self.node(r, e));
+            }
+            l /= 2;
+            e /= 2;
+        }
+        sum
+    }
+    /// Folds all elements in the rectangle `rows x cols`.
+    pub fn query(&self, rows: Range<usize>, cols: Range<usize>) -> T {
+        let h = self.height();
+        let mut sum = self.m.identity();
+        let (mut l, mut e) = (rows.start + h, rows.end + h);
+        while l < e {
+            if l & 1 == 1 {
+                sum = self.m.op(sum, self.query_row(l, &cols));
+                l += 1;
+            }
+            if e & 1 == 1 {
+                e -= 1;
+                sum = self.m.op(sum, self.query_row(e, &cols));
+            }
+            l /= 2;
+            e /= 2;
+        }
+        sum
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::monoid::{Max, Sum};
+
+    #[test]
+    fn max() {
+        let grid = [[3, 1, 4, 1, 5], [9, 2, 6, 5, 3], [5, 8, 9, 7, 9]];
+        let mut st = SegmentTree2D::<Vec<Vec<i32>>, Max>::with_monoid(3, 5, Max);
+        let mut array_st = SegmentTree2D::<[[i32; 5]; 3], Max>::default();
+        for (i, row) in grid.iter().enumerate() {
+            for (j, &x) in row.iter().enumerate() {
+                st.set(i, j, x);
+                array_st.set(i, j, x);
+            }
+        }
+        for i0 in 0..3 {
+            for i1 in i0 + 1..=3 {
+                for j0 in 0..5 {
+                    for j1 in j0 + 1..=5 {
+                        let expected = grid[i0..i1]
+                            .iter()
+                            .flat_map(|r| r[j0..j1].iter())
+                            .max()
+                            .copied()
+                            .unwrap();
+                        assert_eq!(expected, st.query(i0..i1, j0..j1));
+                        assert_eq!(expected, array_st.query(i0..i1, j0..j1));
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn from() {
+        for (h, w) in [(0, 0), (1, 1), (2, 3), (5, 4), (6, 7)] {
+            let grid: Vec<Vec<u64>> = (0..h)
+                .map(|i| (0..w).map(|j| (i * 7 + j * 3) as u64 % 10).collect())
+                .collect();
+            let built = SegmentTree2D::<Vec<Vec<u64>>, _>::from(grid.clone(), |a, b| a + b, || 0);
+            let mut st = SegmentTree2D::<Vec<Vec<u64>>, _>::new(h, w, |a, b| a + b, || 0);
+            for (i, row) in grid.iter().enumerate() {
+                for (j, &x) in row.iter().enumerate() {
+                    st.set(i, j, x);
+                }
+            }
+            assert_eq!(st.q, built.q);
+            assert_eq!((h, w), (built.height(), built.width()));
+        }
+    }
+
+    #[test]
+    fn overwrite() {
+        let mut st = SegmentTree2D::<Vec<Vec<u64>>, Sum>::with_monoid(4, 3, Sum);
+        st.set(1, 1, 5);
+        st.set(3, 2, 7);
+        assert_eq!(12, st.query(0..4, 0..3));
+        st.set(1, 1, 2);
+        assert_eq!(9, st.query(0..4, 0..3));
+        assert_eq!(2, st.query(0..2, 0..2));
+        assert_eq!(7, st.query(2..4, 1..3));
+    }
+}
@@ -0,0 +1,218 @@
+use std::ops::Range;
+
+use crate::monoid::{FnMonoid, Monoid};
+
+/// A segment tree supporting updates of whole ranges in *O*(log *n*).
+///
+/// Besides the monoid `M` of aggregates, the tree takes a monoid `N` of updates and a function
+/// `apply(update, aggregate, len)` that applies an update to the aggregate of a segment containing
+/// `len` elements.
+///
+/// `N::op(later, earlier)` must return the update equivalent to applying `earlier` and then
+/// `later`, and `apply` must distribute over `M::op`, i.e.
+/// `apply(u, op(x, y), lx + ly) == op(apply(u, x, lx), apply(u, y, ly))`.
+#[derive(Debug, Clone)]
+pub struct LazySegmentTree<T, L, M, N, A> {
+    halflen: usize,
+    a: Vec<T>,
+    lazy: Vec<L>,
+    m: M,
+    n: N,
+    apply: A,
+}
+
+impl<T, L, F, U, C, I, A> LazySegmentTree<T, L, FnMonoid<F, U>, FnMonoid<C, I>, A>
+where
+    T: Copy,
+    L: Copy,
+    F: Fn(T, T) -> T,
+    U: Fn() -> T,
+    C: Fn(L, L) -> L,
+    I: Fn() -> L,
+    A: Fn(L, T, usize) -> T,
+{
+    pub fn new(len: usize, f: F, unit: U, compose: C, id: I, apply: A) -> Self {
+        Self::with_monoids(len, FnMonoid(f, unit), FnMonoid(compose, id), apply)
+    }
+    pub fn from<X: ExactSizeIterator<Item = T>>(
+        xs: X,
+        f: F,
+        unit: U,
+        compose: C,
+        id: I,
+        apply: A,
+    ) -> Self {
+        Self::from_monoids(xs, FnMonoid(f, unit), FnMonoid(compose, id), apply)
+    }
+}
+
+impl<T, L, M, N, A> LazySegmentTree<T, L, M, N, A>
+where
+    T: Copy,
+    L: Copy,
+    M: Monoid<T>,
+    N: Monoid<L>,
+    A: Fn(L, T, usize) -> T,
+{
+    pub fn with_monoids(len: usize, m: M, n: N, apply: A) -> Self {
+        let halflen = len.next_power_of_two();
+        Self {
+            halflen,
+            a: vec![m.identity(); halflen * 2],
+            lazy: vec![n.identity(); halflen],
+            m,
+            n,
+            apply,
+        }
+    }
+    pub fn from_monoids<X: ExactSizeIterator<Item = T>>(xs: X, m: M, n: N, apply: A) -> Self {
+        let mut s = Self::with_monoids(xs.len(), m, n, apply);
+        for (v, x) in s.a[s.halflen..].iter_mut().zip(xs) {
+            *v = x;
+        }
+        for i in (1..s.halflen).rev() {
+            s.a[i] = s.m.op(s.a[i * 2], s.a[i * 2 + 1]);
+        }
+        s
+    }
+    /// Applies `update` to every element in `r`.
+    pub fn update(&mut self, r: Range<usize>, update: L) {
+        self._update(r.start, r.end, update, 1, 0, self.halflen)
+    }
+    fn _update(&mut self, start: usize, end: usize, u: L, i: usize, i_start: usize, i_end: usize) {
+        if end <= i_start || start >= i_end {
+            return;
+        }
+        if start <= i_start && i_end <= end {
+            self.apply_node(i, u, i_end - i_start);
+            return;
+        }
+        let mid = i_start + (i_end - i_start) / 2;
+        self.push(i, mid - i_start);
+        self._update(start, end, u, i * 2, i_start, mid);
+        self._update(start, end, u, i * 2 + 1, mid, i_end);
+        self.a[i] = self.m.op(self.a[i * 2], self.a[i * 2 + 1]);
+    }
+    fn apply_node(&mut self, i: usize, u: L, len: usize) {
+        self.a[i] = (self.apply)(u, self.a[i], len);
+        if i < self.halflen {
+            self.lazy[i] = self.n.op(u, self.lazy[i]);
+        }
+    }
+    fn push(&mut self, i: usize, half: usize) {
+        let u = self.lazy[i];
+        self.apply_node(i * 2, u, half);
+        self.apply_node(i * 2 + 1, u, half);
+        self.lazy[i] = self.n.identity();
+    }
+    pub fn query(&self, r: Range<usize>) -> T {
+        self._query(r.start, r.end, 1, 0, self.halflen)
+    }
+    // Pending updates are applied on the way back up instead of being pushed down, which lets
+    // `query` take `&self`.
+    fn _query(&self, start: usize, end: usize, i: usize, i_start: usize, i_end: usize) -> T {
+        if end <= i_start || start >= i_end {
+            self.m.identity()
+        } else if start <= i_start && i_end <= end {
+            self.a[i]
+        } else {
+            let mid = i_start + (i_end - i_start) / 2;
+            let inner = self.m.op(
+                self._query(start, end, i * 2, i_start, mid),
+                self._query(start, end, i * 2 + 1, mid, i_end),
+            );
+            let len = end.min(i_end) - start.max(i_start);
+            (self.apply)(self.lazy[i], inner, len)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn add_min() {
+        let mut st = LazySegmentTree::from(
+            [5, 3, 8, 6, 1, 9].into_iter(),
+            |a: i64, b| a.min(b),
+            || i64::MAX,
+            |a: i64, b| a + b,
+            || 0,
+            |d, x, _| if x == i64::MAX { x } else { x + d },
+        );
+        assert_eq!(1, st.query(0..6));
+        st.update(2..5, 10);
+        assert_eq!(3, st.query(0..6));
+        assert_eq!(11, st.query(2..5));
+        st.update(0..2, 20);
+        assert_eq!(9, st.query(0..6));
+        assert_eq!(11, st.query(3..5));
+        assert_eq!(16, st.query(3..4));
+    }
+
+    #[test]
+    fn named_monoids() {
+        use crate::monoid::{Max, Sum};
+
+        type AddMax = LazySegmentTree<i32, i32, Max, Sum, fn(i32, i32, usize) -> i32>;
+        struct Holder {
+            st: AddMax,
+        }
+        let mut h = Holder {
+            st: LazySegmentTree::from_monoids([1, 7, 2, 3].into_iter(), Max, Sum, |d, x, _| d + x),
+        };
+        h.st.update(2..4, 5);
+        assert_eq!(8, h.st.query(0..4));
+        assert_eq!(8, h.st.query(3..4));
+        assert_eq!(7, h.st.query(0..3));
+    }
+
+    #[test]
+    fn assign_sum() {
+        let mut st = LazySegmentTree::new(
+            7,
+            |a: i64, b| a + b,
+            || 0,
+            |a: Option<i64>, b| a.or(b),
+            || None,
+            |u, x, len| u.map_or(x, |v| v * len as i64),
+        );
+        st.update(0..7, Some(2));
+        assert_eq!(14, st.query(0..7));
+        st.update(2..4, Some(-1));
+        assert_eq!(2 + 2 - 1 - 1 + 2 + 2 + 2, st.query(0..7));
+        assert_eq!(-1 + 2, st.query(3..5));
+        st.update(1..6, Some(0));
+        assert_eq!(4, st.query(0..7));
+        assert_eq!(0, st.query(1..6));
+    }
+
+    #[test]
+    fn add_sum_generated() {
+        use rand::Rng;
+
+        let mut r = rand_pcg::Pcg32::new(0xcafef00dd15ea5e5, 0xa02bdbf7bb3c0a7);
+        let n = 37;
+        let mut xs = vec![0i64; n];
+        let mut st = LazySegmentTree::new(
+            n,
+            |a: i64, b| a + b,
+            || 0,
+            |a: i64, b| a + b,
+            || 0,
+            |d, x, len| x + d * len as i64,
+        );
+        for _ in 0..1000 {
+            let i = r.gen_range(0..n);
+            let j = r.gen_range(i..=n);
+            if r.gen() {
+                let d = r.gen_range(-100..100);
+                xs[i..j].iter_mut().for_each(|x| *x += d);
+                st.update(i..j, d);
+            } else {
+                assert_eq!(xs[i..j].iter().sum::<i64>(), st.query(i..j), "{}..{}", i, j);
+            }
+        }
+    }
+}
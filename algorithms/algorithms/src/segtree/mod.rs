@@ -0,0 +1,378 @@
+use std::ops::Range;
+
+use crate::monoid::{FnMonoid, Monoid};
+
+mod compact;
+mod grid;
+mod lazy;
+mod persistent;
+
+pub use compact::CompactSegmentTree;
+pub use grid::SegmentTree2D;
+pub use lazy::LazySegmentTree;
+pub use persistent::{PersistentSegmentTree, Version};
+
+#[derive(Debug, Clone)]
+pub struct SegmentTree<T, M> {
+    len: usize,
+    halflen: usize,
+    a: Vec<T>,
+    m: M,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Side {
+    Left,
+    Right,
+}
+
+impl<T, F, U> SegmentTree<T, FnMonoid<F, U>>
+where
+    T: Copy,
+    F: Fn(T, T) -> T,
+    U: Fn() -> T,
+{
+    pub fn new(len: usize, f: F, unit: U) -> Self {
+        Self::with_monoid(len, FnMonoid(f, unit))
+    }
+    pub fn from<I: ExactSizeIterator<Item = T>>(xs: I, f: F, unit: U) -> Self {
+        Self::from_monoid(xs, FnMonoid(f, unit))
+    }
+}
+
+impl<T, M> SegmentTree<T, M>
+where
+    T: Copy,
+    M: Monoid<T>,
+{
+    pub fn with_monoid(len: usize, m: M) -> Self {
+        let halflen = len.next_power_of_two();
+        Self {
+            len,
+            halflen,
+            a: vec![m.identity(); halflen * 2],
+            m,
+        }
+    }
+    pub fn from_monoid<I: ExactSizeIterator<Item = T>>(xs: I, m: M) -> Self {
+        let len = xs.len();
+        let halflen = len.next_power_of_two();
+        let mut a = vec![m.identity(); halflen * 2];
+        for (v, x) in a[halflen..].iter_mut().zip(xs) {
+            *v = x;
+        }
+        let mut s = Self { len, halflen, a, m };
+        s.update_all();
+        s
+    }
+    pub fn set(&mut self, index: usize, value: T) {
+        let mut i = self.halflen + index;
+        self.a[i] = value;
+        loop {
+            i = i / 2;
+            self.a[i] = self.m.op(self.a[i * 2], self.a[i * 2 + 1]);
+            if i == 1 {
+                break;
+            }
+        }
+    }
+    pub fn query(&self, r: Range<usize>) -> T {
+        self._query(r.start, r.end, 1, 0, self.halflen)
+    }
+    fn _query(&self, start: usize, end: usize, i: usize, i_start: usize, i_end: usize) -> T {
+        if end <= i_start || start >= i_end {
+            self.m.identity()
+        } else if start <= i_start && i_end <= end {
+            self.a[i]
+        } else {
+            let mid = i_start + (i_end - i_start) / 2;
+            self.m.op(
+                self._query(start, end, i * 2, i_start, mid),
+                self._query(start, end, i * 2 + 1, mid, i_end),
+            )
+        }
+    }
+    pub fn prefer_when(&self, side: Side, p: impl Fn(T) -> bool) -> Option<usize> {
+        let mut i = 1;
+        while i < self.halflen {
+            let l = i * 2;
+            let r = i * 2 + 1;
+            let prefered = if side == Side::Left { l } else { r };
+            let otherwise = l.wrapping_add(r).wrapping_sub(prefered);
+            if p(self.a[prefered]) {
+                i = prefered;
+            } else if p(self.a[otherwise]) {
+                i = otherwise;
+            } else {
+                return None;
+            }
+        }
+        Some(i - self.halflen)
+    }
+    /// Given a start `l` and a predicate `p` where `p(identity)` holds and
+    /// `p(query(l..j))` implies `p(query(l..i))` for all `i <= j`,
+    /// returns the largest `r` such that `p(query(l..r))` is true.
+    ///
+    /// # Time complexity
+    /// *O*(log *n*) calls to `p` and the monoid operation.
+    ///
+    /// # Examples
+    /// To find the first element that is at least 5 at or after index 2:
+    /// ```rust
+    /// # use algorithms::{monoid::Max, SegmentTree};
+    /// let st = SegmentTree::from_monoid([9, 1, 3, 4, 7, 2].into_iter(), Max);
+    /// assert_eq!(4, st.max_right(2, |x| x < 5));
+    /// ```
+    pub fn max_right(&self, l: usize, p: impl Fn(T) -> bool) -> usize {
+        assert!(l <= self.len);
+        if l == self.len {
+            return self.len;
+        }
+        let mut i = l + self.halflen;
+        let mut acc = self.m.identity();
+        loop {
+            while i & 1 == 0 {
+                i /= 2;
+            }
+            if !p(self.m.op(acc, self.a[i])) {
+                while i < self.halflen {
+                    i *= 2;
+                    let next = self.m.op(acc, self.a[i]);
+                    if p(next) {
+                        acc = next;
+                        i += 1;
+                    }
+                }
+                return i - self.halflen;
+            }
+            acc = self.m.op(acc, self.a[i]);
+            i += 1;
+            if i.is_power_of_two() {
+                return self.len;
+            }
+        }
+    }
+    /// Given an end `r` and a predicate `p` where `p(identity)` holds and
+    /// `p(query(i..r))` implies `p(query(j..r))` for all `i <= j`,
+    /// returns the smallest `l` such that `p(query(l..r))` is true.
+    ///
+    /// # Time complexity
+    /// *O*(log *n*) calls to `p` and the monoid operation.
+    ///
+    /// # Examples
+    /// To find the last element that is at least 5 before index 4:
+    /// ```rust
+    /// # use algorithms::{monoid::Max, SegmentTree};
+    /// let st = SegmentTree::from_monoid([9, 1, 6, 4, 7, 2].into_iter(), Max);
+    /// assert_eq!(3, st.min_left(4, |x| x < 5));
+    /// ```
+    pub fn min_left(&self, r: usize, p: impl Fn(T) -> bool) -> usize {
+        assert!(r <= self.len);
+        if r == 0 {
+            return 0;
+        }
+        let mut i = r + self.halflen;
+        let mut acc = self.m.identity();
+        loop {
+            i -= 1;
+            while i > 1 && i & 1 == 1 {
+                i /= 2;
+            }
+            if !p(self.m.op(self.a[i], acc)) {
+                while i < self.halflen {
+                    i = i * 2 + 1;
+                    let next = self.m.op(self.a[i], acc);
+                    if p(next) {
+                        acc = next;
+                        i -= 1;
+                    }
+                }
+                return i + 1 - self.halflen;
+            }
+            acc = self.m.op(self.a[i], acc);
+            if i.is_power_of_two() {
+                return 0;
+            }
+        }
+    }
+    pub fn change_many(&mut self, func: impl FnOnce(&mut [T])) {
+        func(&mut self.a[self.halflen..]);
+        self.update_all();
+    }
+    fn update_all(&mut self) {
+        for i in (1..self.halflen).rev() {
+            self.a[i] = self.m.op(self.a[i * 2], self.a[i * 2 + 1]);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sums() {
+        let mut st = SegmentTree::new(8, |a, b| a + b, || 0);
+        st.set(0, 7);
+        st.set(1, 4);
+        st.set(2, 5);
+        st.set(3, 5);
+        st.set(4, 0);
+        st.set(5, 1);
+        st.set(6, 4);
+        st.set(7, 3);
+        eprintln!("{:?}", st.a);
+        assert_eq!(7 + 4 + 5 + 5 + 1 + 4 + 3, st.query(0..8));
+        assert_eq!(7 + 4 + 5, st.query(0..3));
+        assert_eq!(4 + 5 + 5, st.query(1..4));
+        assert_eq!(5 + 1 + 4, st.query(3..7));
+    }
+
+    #[test]
+    fn min() {
+        let mut st = SegmentTree::new(5, |a: usize, b| a.min(b), || usize::MAX);
+        let xs = [10, 11, 4, 8, 7];
+        for (i, &x) in xs.iter().enumerate() {
+            st.set(i, x);
+        }
+        eprintln!("{:?}", st.a);
+        for i in 0..xs.len() {
+            for j in i..xs.len() {
+                assert_eq!(
+                    *xs[i..j].iter().min().unwrap_or(&usize::MAX),
+                    st.query(i..j),
+                    "{}..{}",
+                    i,
+                    j,
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn from() {
+        let st = SegmentTree::from([4, 9, 1].into_iter(), |a, b| a + b, || 0);
+        eprintln!("{:?}", st.a);
+        assert_eq!(4, st.query(0..1));
+        assert_eq!(9, st.query(1..2));
+        assert_eq!(1, st.query(2..3));
+
+        assert_eq!(13, st.query(0..2));
+        assert_eq!(10, st.query(1..3));
+
+        assert_eq!(14, st.query(0..3));
+    }
+
+    #[test]
+    fn change_many() {
+        let mut st = SegmentTree::from([1, 2, 3, 8].into_iter(), |a, b| a.max(b), || 0);
+        eprintln!("{:?}", st.a);
+        assert_eq!(8, st.query(0..4));
+        st.change_many(|xs| {
+            xs[0] = 5;
+            xs[3] = 2;
+        });
+        eprintln!("{:?}", st.a);
+        assert_eq!(5, st.query(0..4));
+        assert_eq!(3, st.query(2..4));
+    }
+
+    #[test]
+    fn named_monoids() {
+        use crate::monoid::{Gcd, Min, Sum};
+
+        let trees: Vec<SegmentTree<(u32, u32), (Sum, Gcd)>> = vec![
+            SegmentTree::from_monoid([(4, 4), (6, 6), (9, 9)].into_iter(), (Sum, Gcd)),
+            SegmentTree::with_monoid(3, (Sum, Gcd)),
+        ];
+        assert_eq!((19, 1), trees[0].query(0..3));
+        assert_eq!((10, 2), trees[0].query(0..2));
+        assert_eq!((0, 0), trees[1].query(0..3));
+
+        let mut st = SegmentTree::<i64, Min>::with_monoid(4, Min);
+        st.set(2, -5);
+        assert_eq!(-5, st.query(0..4));
+        assert_eq!(i64::MAX, st.query(0..2));
+    }
+
+    #[test]
+    fn prefer_when() {
+        let st = SegmentTree::from([4, 3, 1, 1, 2].into_iter(), |a, b| a.min(b), || usize::MAX);
+        eprintln!("{:?}", st.a);
+        assert_eq!(Some(2), st.prefer_when(Side::Left, |x| x <= 1));
+        assert_eq!(Some(2), st.prefer_when(Side::Left, |x| x <= 2));
+        assert_eq!(Some(1), st.prefer_when(Side::Left, |x| x <= 3));
+        assert_eq!(Some(0), st.prefer_when(Side::Left, |x| x <= 4));
+
+        assert_eq!(Some(3), st.prefer_when(Side::Right, |x| x <= 1));
+        assert_eq!(Some(4), st.prefer_when(Side::Right, |x| x <= 2));
+    }
+
+    #[test]
+    fn max_right_min_left() {
+        let xs = [3, 1, 4, 1, 5, 9, 2, 6, 5];
+        let st = SegmentTree::from(xs.into_iter(), |a, b| a + b, || 0);
+        for i in 0..=xs.len() {
+            for limit in 0..40 {
+                let r = (i..=xs.len())
+                    .take_while(|&j| xs[i..j].iter().sum::<i32>() <= limit)
+                    .last()
+                    .unwrap();
+                assert_eq!(r, st.max_right(i, |x| x <= limit), "{} {}", i, limit);
+                let l = (0..=i)
+                    .rev()
+                    .take_while(|&j| xs[j..i].iter().sum::<i32>() <= limit)
+                    .last()
+                    .unwrap();
+                assert_eq!(l, st.min_left(i, |x| x <= limit), "{} {}", i, limit);
+            }
+        }
+    }
+
+    #[test]
+    fn first_at_least() {
+        let xs = [2, 8, 1, 1, 7, 3, 9];
+        let st = SegmentTree::from(xs.into_iter(), |a: i32, b| a.max(b), || i32::MIN);
+        assert_eq!(1, st.max_right(0, |x| x < 5));
+        assert_eq!(4, st.max_right(2, |x| x < 5));
+        assert_eq!(6, st.max_right(5, |x| x < 5));
+        assert_eq!(7, st.max_right(5, |x| x < 10));
+        assert_eq!(4, st.min_left(6, |x| x < 5) - 1);
+        assert_eq!(0, st.min_left(7, |x| x < 10));
+    }
+
+    extern crate test;
+    use test::{black_box, Bencher};
+
+    // one more than a power of two, which SegmentTree rounds up to almost twice the size
+    const BENCH_LEN: usize = 1024 * 1024 + 1;
+
+    fn query_req<S>(
+        b: &mut Bencher,
+        mut st: S,
+        set: impl Fn(&mut S, usize, i32),
+        query: impl Fn(&S, Range<usize>) -> i32,
+    ) {
+        for i in 0..BENCH_LEN {
+            set(&mut st, i, black_box(69));
+        }
+        let es = [0.1, 0.2, 0.4, 0.6, 0.8, 1.0].map(|f| (BENCH_LEN as f32 * f) as usize);
+        b.iter(|| {
+            for e in es {
+                black_box(query(&st, 0..e));
+            }
+        });
+    }
+
+    #[bench]
+    fn query_req_recursive(b: &mut Bencher) {
+        let st = SegmentTree::new(BENCH_LEN, |a, b| a + b, || 0);
+        query_req(b, st, SegmentTree::set, SegmentTree::query);
+    }
+
+    #[bench]
+    fn query_req_compact(b: &mut Bencher) {
+        let st = CompactSegmentTree::new(BENCH_LEN, |a, b| a + b, || 0);
+        query_req(b, st, CompactSegmentTree::set, CompactSegmentTree::query);
+    }
+}
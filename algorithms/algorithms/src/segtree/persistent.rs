@@ -0,0 +1,195 @@
+use std::ops::Range;
+
+use crate::monoid::{FnMonoid, Monoid};
+
+/// A handle to one version of a [`PersistentSegmentTree`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Version(usize);
+
+#[derive(Debug, Clone, Copy)]
+struct Node<T> {
+    value: T,
+    left: usize,
+    right: usize,
+}
+
+/// A segment tree where every `set` creates a new version, sharing all untouched nodes with the
+/// version it was made from. Every version stays queryable.
+///
+/// Node 0 is a sentinel whose children are itself and whose value is the identity, so a version
+/// only allocates nodes for the paths that have been set.
+#[derive(Debug, Clone)]
+pub struct PersistentSegmentTree<T, M> {
+    len: usize,
+    nodes: Vec<Node<T>>,
+    initial: Version,
+    m: M,
+}
+
+impl<T, F, U> PersistentSegmentTree<T, FnMonoid<F, U>>
+where
+    T: Copy,
+    F: Fn(T, T) -> T,
+    U: Fn() -> T,
+{
+    pub fn new(len: usize, f: F, unit: U) -> Self {
+        Self::with_monoid(len, FnMonoid(f, unit))
+    }
+    pub fn from<I: ExactSizeIterator<Item = T>>(xs: I, f: F, unit: U) -> Self {
+        Self::from_monoid(xs, FnMonoid(f, unit))
+    }
+}
+
+impl<T, M> PersistentSegmentTree<T, M>
+where
+    T: Copy,
+    M: Monoid<T>,
+{
+    pub fn with_monoid(len: usize, m: M) -> Self {
+        Self {
+            len,
+            nodes: vec![Node {
+                value: m.identity(),
+                left: 0,
+                right: 0,
+            }],
+            initial: Version(0),
+            m,
+        }
+    }
+    pub fn from_monoid<I: ExactSizeIterator<Item = T>>(xs: I, m: M) -> Self {
+        let xs: Vec<T> = xs.collect();
+        let mut s = Self::with_monoid(xs.len(), m);
+        if !xs.is_empty() {
+            s.initial = Version(s.build(&xs));
+        }
+        s
+    }
+    fn build(&mut self, xs: &[T]) -> usize {
+        if xs.len() == 1 {
+            return self.push(xs[0], 0, 0);
+        }
+        let mid = xs.len() / 2;
+        let left = self.build(&xs[..mid]);
+        let right = self.build(&xs[mid..]);
+        self.join(left, right)
+    }
+    fn push(&mut self, value: T, left: usize, right: usize) -> usize {
+        self.nodes.push(Node { value, left, right });
+        self.nodes.len() - 1
+    }
+    fn join(&mut self, left: usize, right: usize) -> usize {
+        let value = self.m.op(self.nodes[left].value, self.nodes[right].value);
+        self.push(value, left, right)
+    }
+    /// The version the tree was constructed with.
+    pub fn initial(&self) -> Version {
+        self.initial
+    }
+    pub fn len(&self) -> usize {
+        self.len
+    }
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+    /// Returns a new version equal to `version` except that the element at `index` is `value`.
+    ///
+    /// # Time complexity
+    /// *O*(log *n*), allocating *O*(log *n*) new nodes.
+    pub fn set(&mut self, version: Version, index: usize, value: T) -> Version {
+        assert!(index < self.len);
+        Version(self._set(version.0, index, value, 0, self.len))
+    }
+    fn _set(&mut self, i: usize, index: usize, value: T, i_start: usize, i_end: usize) -> usize {
+        if i_end - i_start == 1 {
+            return self.push(value, 0, 0);
+        }
+        let Node { left, right, .. } = self.nodes[i];
+        let mid = i_start + (i_end - i_start) / 2;
+        if index < mid {
+            let left = self._set(left, index, value, i_start, mid);
+            self.join(left, right)
+        } else {
+            let right = self._set(right, index, value, mid, i_end);
+            self.join(left, right)
+        }
+    }
+    pub fn get(&self, version: Version, index: usize) -> T {
+        self.query(version, index..index + 1)
+    }
+    pub fn query(&self, version: Version, r: Range<usize>) -> T {
+        self._query(r.start, r.end, version.0, 0, self.len)
+    }
+    fn _query(&self, start: usize, end: usize, i: usize, i_start: usize, i_end: usize) -> T {
+        if end <= i_start || start >= i_end || i == 0 {
+            self.m.identity()
+        } else if start <= i_start && i_end <= end {
+            self.nodes[i].value
+        } else {
+            let mid = i_start + (i_end - i_start) / 2;
+            self.m.op(
+                self._query(start, end, self.nodes[i].left, i_start, mid),
+                self._query(start, end, self.nodes[i].right, mid, i_end),
+            )
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::binary_search;
+    use crate::monoid::Sum;
+
+    #[test]
+    fn versions() {
+        let mut st = PersistentSegmentTree::from([1, 2, 3, 4, 5].into_iter(), |a, b| a + b, || 0);
+        let v0 = st.initial();
+        let v1 = st.set(v0, 2, 10);
+        let v2 = st.set(v1, 0, 0);
+        let v3 = st.set(v0, 4, 0);
+        assert_eq!(15, st.query(v0, 0..5));
+        assert_eq!(22, st.query(v1, 0..5));
+        assert_eq!(21, st.query(v2, 0..5));
+        assert_eq!(10, st.query(v3, 0..5));
+        assert_eq!(12, st.query(v1, 1..3));
+        assert_eq!(12, st.query(v2, 1..3));
+        assert_eq!(5, st.query(v0, 1..3));
+        assert_eq!(3, st.get(v3, 2));
+        assert_eq!(10, st.get(v2, 2));
+    }
+
+    #[test]
+    fn sparse() {
+        let mut st = PersistentSegmentTree::new(1_000_000_000, |a: u64, b| a.max(b), || 0);
+        let v = st.set(st.initial(), 123_456_789, 7);
+        let v = st.set(v, 5, 3);
+        assert_eq!(7, st.query(v, 0..1_000_000_000));
+        assert_eq!(3, st.query(v, 0..123_456_789));
+        assert_eq!(0, st.query(st.initial(), 0..1_000_000_000));
+    }
+
+    #[test]
+    fn kth_smallest_in_subarray() {
+        let xs = [5usize, 1, 4, 1, 3, 2, 0, 6];
+        // versions[i] counts the occurrences of every value among xs[..i]
+        let mut st = PersistentSegmentTree::<u32, Sum>::with_monoid(7, Sum);
+        let mut versions = vec![st.initial()];
+        for &x in &xs {
+            let prev = *versions.last().unwrap();
+            let count = st.get(prev, x);
+            versions.push(st.set(prev, x, count + 1));
+        }
+        for l in 0..xs.len() {
+            for r in l + 1..=xs.len() {
+                let mut sorted = xs[l..r].to_vec();
+                sorted.sort();
+                for (k, &expected) in sorted.iter().enumerate() {
+                    let count = |v| st.query(versions[r], 0..v) - st.query(versions[l], 0..v);
+                    let v = binary_search(0..7, |v| count(v + 1) as usize <= k);
+                    assert_eq!(expected, v, "{}..{} k={}", l, r, k);
+                }
+            }
+        }
+    }
+}
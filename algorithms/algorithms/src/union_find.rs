@@ -1,15 +1,19 @@
 use std::ops::{Index, IndexMut};
 
+use crate::num::AddGroup;
+
 pub struct UnionFind<A = Vec<usize>> {
     parent: A,
-    rank: A,
+    size: A,
+    components: usize,
 }
 
 impl UnionFind {
     pub fn new(size: usize) -> Self {
         Self {
             parent: (0..size).collect(),
-            rank: vec![0; size],
+            size: vec![1; size],
+            components: size,
         }
     }
 }
@@ -22,15 +26,23 @@ impl<const N: usize> UnionFind<[usize; N]> {
         }
         Self {
             parent,
-            rank: [0; N],
+            size: [1; N],
+            components: N,
         }
     }
 }
 
+impl<const N: usize> Default for UnionFind<[usize; N]> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl<A> UnionFind<A>
 where
     A: Index<usize, Output = usize>,
     A: IndexMut<usize>,
+    A: AsRef<[usize]>, // for .len()
 {
     pub fn root(&mut self, mut node: usize) -> usize {
         let mut r = node;
@@ -45,24 +57,292 @@ where
         r
     }
 
-    pub fn join(&mut self, a: usize, b: usize) {
+    /// Joins the sets containing `a` and `b`, returning whether they were disjoint.
+    pub fn join(&mut self, a: usize, b: usize) -> bool {
         let mut a = self.root(a);
         let mut b = self.root(b);
 
         if a == b {
-            return;
+            return false;
+        }
+
+        if self.size[a] < self.size[b] {
+            std::mem::swap(&mut a, &mut b);
+        }
+
+        self.parent[b] = a;
+        self.size[a] += self.size[b];
+        self.components -= 1;
+        true
+    }
+
+    pub fn same(&mut self, a: usize, b: usize) -> bool {
+        self.root(a) == self.root(b)
+    }
+
+    /// The number of elements in the set containing `node`.
+    pub fn size_of(&mut self, node: usize) -> usize {
+        let r = self.root(node);
+        self.size[r]
+    }
+
+    pub fn num_components(&self) -> usize {
+        self.components
+    }
+
+    /// All sets, each sorted and ordered by their smallest element.
+    pub fn groups(&mut self) -> impl Iterator<Item = Vec<usize>> {
+        let len = self.parent.as_ref().len();
+        let mut index = vec![usize::MAX; len];
+        let mut groups: Vec<Vec<usize>> = Vec::with_capacity(self.components);
+        for node in 0..len {
+            let r = self.root(node);
+            if index[r] == usize::MAX {
+                index[r] = groups.len();
+                groups.push(Vec::with_capacity(self.size[r]));
+            }
+            groups[index[r]].push(node);
+        }
+        groups.into_iter()
+    }
+}
+
+/// A union find keeping track of the potential difference `pot[b] - pot[a]` between any two nodes
+/// `a` and `b` in the same set. The potentials must form a group, since differences are taken in
+/// both directions.
+pub struct WeightedUnionFind<T> {
+    parent: Vec<usize>,
+    size: Vec<usize>,
+    // pot[node] - pot[parent[node]]
+    diff: Vec<T>,
+}
+
+impl<T: AddGroup> WeightedUnionFind<T> {
+    pub fn new(size: usize) -> Self {
+        Self {
+            parent: (0..size).collect(),
+            size: vec![1; size],
+            diff: vec![T::ZERO; size],
+        }
+    }
+
+    /// Returns the root of `node` together with `pot[node] - pot[root]`.
+    pub fn root(&mut self, mut node: usize) -> (usize, T) {
+        let mut r = node;
+        let mut d = T::ZERO;
+        while r != self.parent[r] {
+            d = d + self.diff[r];
+            r = self.parent[r];
+        }
+        // rest is pot[node] - pot[r] for every node along the path
+        let mut rest = d;
+        while node != r {
+            let (p, step) = (self.parent[node], self.diff[node]);
+            self.parent[node] = r;
+            self.diff[node] = rest;
+            rest = rest - step;
+            node = p;
+        }
+        (r, d)
+    }
+
+    /// Records that `pot[b] - pot[a] == w`.
+    ///
+    /// Returns whether the sets of `a` and `b` were disjoint, or, if they already were in the same
+    /// set and the recorded difference isn't `w`, `Err` with the recorded difference.
+    pub fn join(&mut self, a: usize, b: usize, w: T) -> Result<bool, T>
+    where
+        T: PartialEq,
+    {
+        let (ra, da) = self.root(a);
+        let (rb, db) = self.root(b);
+
+        if ra == rb {
+            let d = db - da;
+            return if d == w { Ok(false) } else { Err(d) };
+        }
+
+        // pot[rb] - pot[ra] == (pot[b] - db) - (pot[a] - da)
+        if self.size[ra] < self.size[rb] {
+            self.parent[ra] = rb;
+            self.diff[ra] = db - (w + da);
+            self.size[rb] += self.size[ra];
+        } else {
+            self.parent[rb] = ra;
+            self.diff[rb] = (w + da) - db;
+            self.size[ra] += self.size[rb];
+        }
+        Ok(true)
+    }
+
+    pub fn same(&mut self, a: usize, b: usize) -> bool {
+        self.root(a).0 == self.root(b).0
+    }
+
+    /// `pot[b] - pot[a]`, if `a` and `b` are in the same set.
+    pub fn diff(&mut self, a: usize, b: usize) -> Option<T> {
+        let (ra, da) = self.root(a);
+        let (rb, db) = self.root(b);
+        (ra == rb).then(|| db - da)
+    }
+}
+
+/// A union find without path compression, so that joins can be undone in reverse order.
+pub struct RollbackUnionFind {
+    parent: Vec<usize>,
+    rank: Vec<usize>,
+    components: usize,
+    // (new parent, joined root, whether the rank of the new parent was increased)
+    history: Vec<(usize, usize, bool)>,
+}
+
+impl RollbackUnionFind {
+    pub fn new(size: usize) -> Self {
+        Self {
+            parent: (0..size).collect(),
+            rank: vec![0; size],
+            components: size,
+            history: vec![],
+        }
+    }
+
+    /// *O*(log *n*) since the trees are balanced by rank.
+    pub fn root(&self, mut node: usize) -> usize {
+        while node != self.parent[node] {
+            node = self.parent[node];
+        }
+        node
+    }
+
+    pub fn join(&mut self, a: usize, b: usize) -> bool {
+        let mut a = self.root(a);
+        let mut b = self.root(b);
+
+        if a == b {
+            return false;
         }
 
         if self.rank[a] < self.rank[b] {
-            let tmp = a;
-            a = b;
-            b = tmp;
+            std::mem::swap(&mut a, &mut b);
         }
 
         self.parent[b] = a;
+        let inc = self.rank[a] == self.rank[b];
+        if inc {
+            self.rank[a] += 1;
+        }
+        self.components -= 1;
+        self.history.push((a, b, inc));
+        true
+    }
+
+    pub fn same(&self, a: usize, b: usize) -> bool {
+        self.root(a) == self.root(b)
+    }
+
+    pub fn num_components(&self) -> usize {
+        self.components
+    }
+
+    /// A point in time that can be passed to `rollback`.
+    pub fn snapshot(&self) -> usize {
+        self.history.len()
+    }
+
+    /// Undoes all joins made after `snapshot` was taken.
+    pub fn rollback(&mut self, snapshot: usize) {
+        while self.history.len() > snapshot {
+            let (a, b, inc) = self.history.pop().unwrap();
+            self.parent[b] = b;
+            if inc {
+                self.rank[a] -= 1;
+            }
+            self.components += 1;
+        }
+    }
+}
+
+/// A partially persistent union find: joins can only be made to the latest version, but
+/// connectivity can be asked about any earlier point in time, where time `t` is the state after the
+/// first `t` calls to `join`.
+///
+/// There is no path compression, instead every parent link remembers when it was set. Since the
+/// trees are balanced by rank, following links only set at or before `t` takes *O*(log *n*).
+pub struct PersistentUnionFind {
+    parent: Vec<usize>,
+    rank: Vec<usize>,
+    // the time at which parent[node] was set, or usize::MAX for roots
+    time: Vec<usize>,
+    now: usize,
+}
+
+impl PersistentUnionFind {
+    pub fn new(size: usize) -> Self {
+        Self {
+            parent: (0..size).collect(),
+            rank: vec![0; size],
+            time: vec![usize::MAX; size],
+            now: 0,
+        }
+    }
+
+    /// The number of joins made so far.
+    pub fn now(&self) -> usize {
+        self.now
+    }
+
+    /// The root of `node` after the first `t` joins.
+    pub fn root_at(&self, mut node: usize, t: usize) -> usize {
+        while self.time[node] <= t {
+            node = self.parent[node];
+        }
+        node
+    }
+
+    pub fn join(&mut self, a: usize, b: usize) -> bool {
+        self.now += 1;
+        let mut a = self.root_at(a, self.now);
+        let mut b = self.root_at(b, self.now);
+
+        if a == b {
+            return false;
+        }
+
+        if self.rank[a] < self.rank[b] {
+            std::mem::swap(&mut a, &mut b);
+        }
+
+        self.parent[b] = a;
+        self.time[b] = self.now;
         if self.rank[a] == self.rank[b] {
             self.rank[a] += 1;
         }
+        true
+    }
+
+    /// Whether `a` and `b` were connected after the first `t` joins.
+    pub fn same_at(&self, a: usize, b: usize, t: usize) -> bool {
+        self.root_at(a, t) == self.root_at(b, t)
+    }
+
+    /// The smallest `t` such that `same_at(a, b, t)`, or `None` if they aren't connected yet.
+    pub fn first_connected_time(&self, mut a: usize, mut b: usize) -> Option<usize> {
+        // Links are set in increasing order of time going up the trees, so the answer is the
+        // latest link on the path between a and b.
+        let mut t = 0;
+        while a != b {
+            let next = if self.time[a] < self.time[b] {
+                &mut a
+            } else {
+                &mut b
+            };
+            if self.time[*next] == usize::MAX {
+                return None;
+            }
+            t = t.max(self.time[*next]);
+            *next = self.parent[*next];
+        }
+        Some(t)
     }
 }
 
@@ -91,4 +371,125 @@ mod tests {
         assert_eq!(uf.root(1), uf.root(2));
         assert_eq!(uf.root(0), uf.root(2));
     }
+
+    #[test]
+    fn sizes_and_components() {
+        let mut uf = UnionFind::<Vec<usize>>::new(6);
+        assert_eq!(6, uf.num_components());
+        assert!(uf.join(0, 3));
+        assert!(uf.join(4, 3));
+        assert!(!uf.join(0, 4));
+        assert!(uf.join(1, 5));
+        assert_eq!(3, uf.num_components());
+        assert_eq!(3, uf.size_of(4));
+        assert_eq!(2, uf.size_of(1));
+        assert_eq!(1, uf.size_of(2));
+        assert!(uf.same(0, 4));
+        assert!(!uf.same(0, 5));
+        assert_eq!(
+            vec![vec![0, 3, 4], vec![1, 5], vec![2]],
+            uf.groups().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn weighted() {
+        let mut uf = WeightedUnionFind::<i64>::new(6);
+        assert_eq!(Ok(true), uf.join(0, 1, 5));
+        assert_eq!(Ok(true), uf.join(2, 1, 2));
+        assert_eq!(Some(3), uf.diff(0, 2));
+        assert_eq!(Some(-3), uf.diff(2, 0));
+        assert_eq!(Ok(false), uf.join(0, 2, 3));
+        assert_eq!(Err(3), uf.join(0, 2, 4));
+        assert_eq!(None, uf.diff(0, 3));
+        assert_eq!(Ok(true), uf.join(4, 3, -1));
+        assert_eq!(Ok(true), uf.join(3, 1, 10));
+        assert_eq!(Some(9), uf.diff(4, 1));
+        assert_eq!(Some(7), uf.diff(4, 2));
+        assert_eq!(Some(4), uf.diff(4, 0));
+        assert_eq!(Some(0), uf.diff(5, 5));
+        assert!(!uf.same(5, 0));
+    }
+
+    #[test]
+    fn weighted_generated() {
+        use rand::Rng;
+
+        let mut r = rand_pcg::Pcg32::new(0xcafef00dd15ea5e5, 0xa02bdbf7bb3c0a7);
+        let pot: Vec<i32> = (0..50).map(|_| r.gen_range(-1000..1000)).collect();
+        let mut uf = WeightedUnionFind::new(pot.len());
+        for _ in 0..200 {
+            let (a, b) = (r.gen_range(0..pot.len()), r.gen_range(0..pot.len()));
+            if r.gen() {
+                assert!(uf.join(a, b, pot[b] - pot[a]).is_ok());
+                assert!(uf.join(a, b, pot[b] - pot[a] + 1).is_err());
+            } else if let Some(d) = uf.diff(a, b) {
+                assert_eq!(pot[b] - pot[a], d);
+            }
+        }
+    }
+
+    #[test]
+    fn rollback() {
+        let mut uf = RollbackUnionFind::new(5);
+        assert!(uf.join(0, 1));
+        let s = uf.snapshot();
+        assert!(uf.join(2, 3));
+        assert!(uf.join(1, 3));
+        assert!(!uf.join(0, 2));
+        assert!(uf.same(0, 3));
+        assert_eq!(2, uf.num_components());
+        uf.rollback(s);
+        assert!(uf.same(0, 1));
+        assert!(!uf.same(0, 3));
+        assert!(!uf.same(2, 3));
+        assert_eq!(4, uf.num_components());
+        assert!(uf.join(3, 4));
+        uf.rollback(0);
+        assert_eq!(5, uf.num_components());
+        assert!(!uf.same(0, 1));
+    }
+
+    #[test]
+    fn persistent() {
+        use rand::Rng;
+
+        let mut r = rand_pcg::Pcg32::new(0xcafef00dd15ea5e5, 0xa02bdbf7bb3c0a7);
+        let n = 20;
+        let joins: Vec<(usize, usize)> = (0..30)
+            .map(|_| (r.gen_range(0..n), r.gen_range(0..n)))
+            .collect();
+        let mut puf = PersistentUnionFind::new(n);
+        for &(a, b) in &joins {
+            puf.join(a, b);
+        }
+        assert_eq!(joins.len(), puf.now());
+
+        let mut uf = UnionFind::<Vec<usize>>::new(n);
+        let mut first = std::collections::HashMap::new();
+        for t in 0..=joins.len() {
+            if t > 0 {
+                uf.join(joins[t - 1].0, joins[t - 1].1);
+            }
+            for a in 0..n {
+                for b in 0..n {
+                    assert_eq!(uf.same(a, b), puf.same_at(a, b, t));
+                    if uf.same(a, b) {
+                        first.entry((a, b)).or_insert(t);
+                    }
+                }
+            }
+        }
+        for a in 0..n {
+            for b in 0..n {
+                assert_eq!(
+                    first.get(&(a, b)).copied(),
+                    puf.first_connected_time(a, b),
+                    "{} {}",
+                    a,
+                    b
+                );
+            }
+        }
+    }
 }
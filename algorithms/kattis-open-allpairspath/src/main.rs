@@ -0,0 +1,50 @@
+// https://open.kattis.com/problems/allpairspath
+
+use algorithms::graph::{floyd_warshall, Distance, GraphBuilder};
+use std::fmt::Write;
+use std::io::{self, Read};
+
+fn main() {
+    let mut input = String::new();
+    io::stdin().lock().read_to_string(&mut input).unwrap();
+    let mut ints = input
+        .split(|c| c == ' ' || c == '\n')
+        .map(|s| s.trim().parse::<i64>().unwrap());
+    let mut output = String::with_capacity(11000);
+
+    loop {
+        let (n, m, q) = (
+            ints.next().unwrap() as usize,
+            ints.next().unwrap(),
+            ints.next().unwrap(),
+        );
+        if n == 0 {
+            break;
+        }
+
+        let mut g = GraphBuilder::new(n);
+
+        for _ in 0..m {
+            let (u, v, w) = (
+                ints.next().unwrap() as usize,
+                ints.next().unwrap() as usize,
+                ints.next().unwrap(),
+            );
+
+            g.add_edge(u, v, w);
+        }
+
+        let paths = floyd_warshall(&g.build());
+
+        for _ in 0..q {
+            let (u, v) = (ints.next().unwrap() as usize, ints.next().unwrap() as usize);
+            match paths.dist(u, v) {
+                Distance::Finite(d) => writeln!(&mut output, "{}", d).unwrap(),
+                Distance::NegInfinity => writeln!(&mut output, "-Infinity").unwrap(),
+                Distance::Unreachable => writeln!(&mut output, "Impossible").unwrap(),
+            }
+        }
+    }
+
+    print!("{}", output);
+}
@@ -0,0 +1,34 @@
+// https://open.kattis.com/problems/equivalences
+
+use std::{
+    fmt::Write,
+    io::{stdin, Read},
+};
+
+use algorithms::graph::{strongly_connected_components, GraphBuilder};
+
+fn main() {
+    let mut input = String::new();
+    stdin().lock().read_to_string(&mut input).unwrap();
+    let mut ints = input
+        .trim()
+        .split(|c| c == ' ' || c == '\n')
+        .map(|s| s.parse::<usize>().unwrap());
+    let mut output = String::new();
+
+    for _case in 0..ints.next().unwrap() {
+        let (n, m) = (ints.next().unwrap(), ints.next().unwrap());
+
+        let mut g = GraphBuilder::new(n);
+        for _ in 0..m {
+            let (a, b) = (ints.next().unwrap() - 1, ints.next().unwrap() - 1);
+            g.add_edge(a, b, ());
+        }
+
+        // https://en.wikipedia.org/wiki/Strong_connectivity_augmentation#Unweighted_version
+        let scc = strongly_connected_components(&g.build());
+        writeln!(output, "{}", scc.edges_to_strongly_connect()).unwrap();
+    }
+
+    print!("{}", output);
+}
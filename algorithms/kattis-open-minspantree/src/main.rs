@@ -0,0 +1,50 @@
+// https://open.kattis.com/problems/minspantree
+
+use std::fmt::Write;
+use std::io::{stdin, Read};
+
+use algorithms::graph::{mst::kruskal, GraphBuilder};
+
+fn main() {
+    let mut input = String::new();
+    stdin().lock().read_to_string(&mut input).unwrap();
+    let mut ints = input
+        .split(|c| c == '\n' || c == ' ')
+        .map(|i| i.parse::<i32>().unwrap());
+    let mut output = String::with_capacity(4096);
+
+    loop {
+        let (n, m) = (ints.next().unwrap() as usize, ints.next().unwrap() as usize);
+        if n == 0 {
+            break;
+        }
+
+        let mut g = GraphBuilder::new(n);
+        for _ in 0..m {
+            let (u, v, w) = (
+                ints.next().unwrap() as usize,
+                ints.next().unwrap() as usize,
+                ints.next().unwrap(),
+            );
+            g.add_undirected_edge(u, v, w);
+        }
+
+        let forest = kruskal(&g.build());
+        if forest.is_tree() {
+            let mut ue: Vec<_> = forest
+                .edges()
+                .iter()
+                .map(|&(u, v, _)| (u.min(v), u.max(v)))
+                .collect();
+            ue.sort();
+            writeln!(output, "{}", forest.weight()).unwrap();
+            for (u, v) in ue {
+                writeln!(output, "{} {}", u, v).unwrap();
+            }
+        } else {
+            writeln!(output, "Impossible").unwrap();
+        }
+    }
+
+    print!("{}", output);
+}
@@ -1,12 +1,8 @@
 // https://open.kattis.com/problems/shortestpath1
 
+use algorithms::graph::{dijkstra, GraphBuilder};
 use std::fmt::Write;
-use std::u32;
-use std::{
-    cmp::Reverse,
-    collections::BinaryHeap,
-    io::{self, Read},
-};
+use std::io::{self, Read};
 
 fn main() {
     let mut buf = String::new();
@@ -31,7 +27,7 @@ fn main() {
             break;
         }
 
-        let mut adjlist = (0..n).map(|_| vec![]).collect::<Vec<Vec<(usize, u32)>>>();
+        let mut g = GraphBuilder::new(n);
 
         for mut edge in (&mut lines).take(m).map(|s| s.split(' ')) {
             let (u, v, w) = (
@@ -39,28 +35,15 @@ fn main() {
                 edge.next().unwrap().parse::<usize>().unwrap(),
                 edge.next().unwrap().parse::<u32>().unwrap(),
             );
-            adjlist[u].push((v, w));
+            g.add_edge(u, v, w);
         }
 
-        let mut dist = [u32::MAX; 10000];
-        let mut heap = BinaryHeap::new();
-        heap.push((Reverse(0), s));
-        dist[s] = 0;
-
-        while let Some((d, node)) = heap.pop().map(|r| (r.0 .0, r.1)) {
-            for &(neighbor, w) in &adjlist[node] {
-                if d + w < dist[neighbor] {
-                    dist[neighbor] = d + w;
-                    heap.push((Reverse(d + w), neighbor));
-                }
-            }
-        }
+        let paths = dijkstra(&g.build(), s);
 
         for q in (&mut lines).take(q).map(|i| i.parse::<usize>().unwrap()) {
-            if dist[q] == u32::MAX {
-                writeln!(&mut out, "Impossible").unwrap();
-            } else {
-                writeln!(&mut out, "{}", dist[q]).unwrap();
+            match paths.dist(q) {
+                Some(d) => writeln!(&mut out, "{}", d).unwrap(),
+                None => writeln!(&mut out, "Impossible").unwrap(),
             }
         }
     }
@@ -25,17 +25,10 @@ fn main() {
             .map(|l| l.trim().parse::<usize>().unwrap());
         let (a, b) = (ab.next().unwrap(), ab.next().unwrap());
         match op {
-            "=" => uf.join(a, b),
-            "?" => writeln!(
-                output,
-                "{}",
-                if uf.root(a) == uf.root(b) {
-                    "yes"
-                } else {
-                    "no"
-                }
-            )
-            .unwrap(),
+            "=" => {
+                uf.join(a, b);
+            }
+            "?" => writeln!(output, "{}", if uf.same(a, b) { "yes" } else { "no" }).unwrap(),
             _ => (),
         }
     }